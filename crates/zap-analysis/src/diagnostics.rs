@@ -0,0 +1,102 @@
+use zap_language::{
+    ast::{AstNode, OptionDecl},
+    tree_sitter::Range,
+};
+
+use crate::{
+    model::{SemanticModel, SymbolKind},
+    source::Source,
+};
+
+pub use zap_language::diagnostics::{Diagnostic, LabelStyle, Severity};
+
+/**
+    Runs the Zap compiler on the source, returning all of its diagnostics.
+
+    Crashes inside of the compiler are reported as a diagnostic, but there is no
+    time limit, and the compiler may never finish for some sources. Callers that
+    need to stay responsive should run this on a separate thread, with a time limit.
+*/
+#[must_use]
+pub fn diagnostics(src: &Source) -> Vec<Diagnostic> {
+    zap_language::diagnostics::parse(src.text())
+}
+
+/**
+    A type or namespace declaration that is never referenced.
+*/
+#[derive(Debug, Clone)]
+pub struct UnusedDeclaration {
+    /// Either `SymbolKind::Type` or `SymbolKind::Namespace`
+    pub kind: SymbolKind,
+    /// `C`
+    pub name: String,
+    /// `C`
+    pub identifier: Range,
+    /// `type C = u32`
+    pub declaration: Range,
+}
+
+/**
+    Finds all type and namespace declarations that are never referenced.
+
+    The model must have been built from the given source.
+*/
+#[must_use]
+pub fn unused_declarations(src: &Source, model: &SemanticModel) -> Vec<UnusedDeclaration> {
+    // Types are all exported to Luau when there is a types output
+    // file, meaning we can not know if they are used or not
+    let check_types = !has_option(src, "types_output");
+
+    let symbols = model.symbols().collect::<Vec<_>>();
+    let mut unused = Vec::new();
+
+    for (id, symbol) in &symbols {
+        let is_used = match symbol.kind {
+            SymbolKind::Type if check_types => model
+                .references_to(*id)
+                .any(|r| !is_inside(r.reference, symbol.declaration)),
+            // Namespaces containing events or functions are always used,
+            // since those are a part of the generated networking API, and
+            // the same goes for types whenever those are exported to Luau
+            SymbolKind::Namespace => {
+                model.references_to(*id).next().is_some()
+                    || symbols.iter().any(|(_, other)| {
+                        let is_exported = match other.kind {
+                            SymbolKind::Event | SymbolKind::Function => true,
+                            SymbolKind::Type => !check_types,
+                            _ => false,
+                        };
+                        is_exported && is_inside(other.declaration, symbol.declaration)
+                    })
+            }
+            _ => true,
+        };
+        if !is_used {
+            unused.push(UnusedDeclaration {
+                kind: symbol.kind,
+                name: symbol.name.clone(),
+                identifier: symbol.identifier,
+                declaration: symbol.declaration,
+            });
+        }
+    }
+
+    unused
+}
+
+fn has_option(src: &Source, name: &str) -> bool {
+    let root = src.root();
+    let mut cursor = root.walk();
+    root.children(&mut cursor)
+        .filter_map(OptionDecl::cast)
+        .any(|option| {
+            option
+                .name()
+                .is_some_and(|ident| src.node_text(ident) == name)
+        })
+}
+
+fn is_inside(range: Range, outer: Range) -> bool {
+    range.start_byte >= outer.start_byte && range.end_byte <= outer.end_byte
+}
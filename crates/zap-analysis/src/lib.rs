@@ -0,0 +1,18 @@
+mod diagnostics;
+mod model;
+mod source;
+mod structs;
+mod utils;
+
+pub use self::diagnostics::{
+    Diagnostic, LabelStyle, Severity, UnusedDeclaration, diagnostics, unused_declarations,
+};
+pub use self::model::{
+    Occurrence, Reference, Scope, ScopeId, SemanticModel, Symbol, SymbolId, SymbolKind,
+};
+pub use self::source::Source;
+pub use self::structs::{DeclaredNamespace, DeclaredType, ReferencedNamespace, ReferencedType};
+pub use self::utils::{
+    doc_comment, is_namespace, is_type, is_type_primitive, is_type_reference, namespace_path,
+    qualified_type_text, replace_bytes,
+};
@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+
+use zap_language::{
+    tree_sitter::{Node, Point, Range},
+    tree_sitter_utils::AncestorIterator,
+};
+
+use crate::{
+    source::Source,
+    structs::{ReferencedNamespace, ReferencedType},
+};
+
+pub type ScopeId = usize;
+pub type SymbolId = usize;
+
+/**
+    The kind of a declared symbol.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Namespace,
+    Type,
+    Event,
+    Function,
+}
+
+impl SymbolKind {
+    fn from_node_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "namespace_declaration" => Some(Self::Namespace),
+            "type_declaration" => Some(Self::Type),
+            "event_declaration" => Some(Self::Event),
+            "function_declaration" => Some(Self::Function),
+            _ => None,
+        }
+    }
+
+    fn node_kind(self) -> &'static str {
+        match self {
+            Self::Namespace => "namespace_declaration",
+            Self::Type => "type_declaration",
+            Self::Event => "event_declaration",
+            Self::Function => "function_declaration",
+        }
+    }
+}
+
+/**
+    A declared namespace, type, event or function.
+*/
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    /// `C`
+    pub name: String,
+    /// `type C = u32`
+    pub declaration: Range,
+    /// `C`
+    pub identifier: Range,
+    /// The scope that the symbol is declared in
+    pub scope: ScopeId,
+}
+
+/**
+    The scope of the root of a document, or of a single namespace.
+
+    Types and namespaces are only ever visible in the scope they are
+    declared in, and through qualified paths from any outer scope.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    /// The namespace that owns this scope, `None` for the root
+    pub owner: Option<SymbolId>,
+    /// The scope that this scope is nested in, `None` for the root
+    pub parent: Option<ScopeId>,
+    namespaces: HashMap<String, (SymbolId, ScopeId)>,
+    types: HashMap<String, SymbolId>,
+}
+
+impl Scope {
+    /**
+        Finds a namespace declared directly in this scope, and the scope of that namespace.
+    */
+    #[must_use]
+    pub fn find_namespace(&self, name: &str) -> Option<(SymbolId, ScopeId)> {
+        self.namespaces.get(name).copied()
+    }
+
+    /**
+        Finds a type declared directly in this scope.
+    */
+    #[must_use]
+    pub fn find_type(&self, name: &str) -> Option<SymbolId> {
+        self.types.get(name).copied()
+    }
+}
+
+/**
+    A reference to a type or namespace, resolved if possible.
+
+    ### Example
+
+    ```zap
+    type Ref = A.B.C
+    ```
+
+    Contains a type reference to `C`, and namespace references to `A` and `B`.
+*/
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub kind: SymbolKind,
+    /// `A.B.C`
+    pub reference: Range,
+    /// `C` for the type reference, `A` or `B` for namespace references
+    pub identifier: Range,
+    /// The declaration that the reference resolves to, if any
+    pub target: Option<SymbolId>,
+}
+
+/**
+    A declaration or resolved reference of a symbol, at some point in a source.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Occurrence {
+    pub symbol: SymbolId,
+    pub is_declaration: bool,
+    /// The identifier at the point
+    pub identifier: Range,
+    /// The full reference for type references, such as `A.B.C`, otherwise the identifier
+    pub range: Range,
+}
+
+/**
+    A semantic model of a single source, containing its scopes, all declared
+    symbols, and all references, resolved to the symbols they refer to.
+
+    Building the model walks the tree once, and resolves each reference once,
+    so any lookups afterwards are cheap - the model can be reused until the source changes.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct SemanticModel {
+    scopes: Vec<Scope>,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+    references_by_target: HashMap<SymbolId, Vec<usize>>,
+}
+
+impl SemanticModel {
+    /**
+        Builds the semantic model for the given document.
+    */
+    #[must_use]
+    pub fn build(src: &Source) -> Self {
+        let mut model = Self::default();
+        let root = src.root();
+
+        // 1. Declare all symbols, in all scopes, so that references can be
+        //    resolved regardless of the order that things are declared in
+        model.scopes.push(Scope::default());
+        let mut referencing = Vec::new();
+        model.declare_in(src, root, 0, &mut referencing);
+
+        // 2. Resolve all references inside of types, events and functions
+        for (scope, node) in referencing {
+            for typ in ReferencedType::find_all_in(node) {
+                let target = model
+                    .resolve_path(scope, &typ.namespace_texts(src))
+                    .and_then(|s| model.scopes[s].find_type(&typ.identifier_text(src)));
+                model.add_reference(Reference {
+                    kind: SymbolKind::Type,
+                    reference: typ.reference_range(),
+                    identifier: typ.identifier_range(),
+                    target,
+                });
+            }
+            for ns in ReferencedNamespace::find_all_in(node) {
+                let target = model
+                    .resolve_path(scope, &ns.namespace_texts(src))
+                    .and_then(|s| model.scopes[s].find_namespace(&ns.identifier_text(src)))
+                    .map(|(id, _)| id);
+                model.add_reference(Reference {
+                    kind: SymbolKind::Namespace,
+                    reference: ns.reference_range(),
+                    identifier: ns.identifier_range(),
+                    target,
+                });
+            }
+        }
+
+        model
+    }
+
+    fn declare_in<'a>(
+        &mut self,
+        src: &Source,
+        namespace: Node<'a>,
+        scope: ScopeId,
+        referencing: &mut Vec<(ScopeId, Node<'a>)>,
+    ) {
+        let mut cursor = namespace.walk();
+        for child in namespace.children(&mut cursor) {
+            let Some(kind) = SymbolKind::from_node_kind(child.kind()) else {
+                continue;
+            };
+            let Some(identifier) = child.child_by_field_name("name") else {
+                continue;
+            };
+
+            let id = self.symbols.len();
+            let name = src.node_text(identifier);
+            self.symbols.push(Symbol {
+                kind,
+                name: name.clone(),
+                declaration: child.range(),
+                identifier: identifier.range(),
+                scope,
+            });
+
+            // Duplicate declarations are diagnosed by Zap,
+            // and references always resolve to the first one
+            match kind {
+                SymbolKind::Namespace => {
+                    let inner = self.scopes.len();
+                    self.scopes.push(Scope {
+                        owner: Some(id),
+                        parent: Some(scope),
+                        ..Default::default()
+                    });
+                    self.scopes[scope]
+                        .namespaces
+                        .entry(name)
+                        .or_insert((id, inner));
+                    self.declare_in(src, child, inner, referencing);
+                }
+                SymbolKind::Type => {
+                    self.scopes[scope].types.entry(name).or_insert(id);
+                    referencing.push((scope, child));
+                }
+                SymbolKind::Event | SymbolKind::Function => {
+                    referencing.push((scope, child));
+                }
+            }
+        }
+    }
+
+    fn add_reference(&mut self, reference: Reference) {
+        let index = self.references.len();
+        if let Some(target) = reference.target {
+            self.references_by_target
+                .entry(target)
+                .or_default()
+                .push(index);
+        }
+        self.references.push(reference);
+    }
+
+    /**
+        Walks the given namespace path, starting at the given scope,
+        returning the scope of the final namespace in the path.
+
+        This is how all references are resolved - an empty path resolves
+        to the starting scope, and namespaces are only ever walked into.
+    */
+    #[must_use]
+    pub fn resolve_path(&self, scope: ScopeId, path: &[String]) -> Option<ScopeId> {
+        path.iter().try_fold(scope, |current, name| {
+            self.scopes[current]
+                .find_namespace(name)
+                .map(|(_, inner)| inner)
+        })
+    }
+
+    /**
+        Returns the symbol with the given id.
+    */
+    #[must_use]
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id]
+    }
+
+    /**
+        Returns all declared symbols, in the order they are declared in.
+    */
+    pub fn symbols(&self) -> impl Iterator<Item = (SymbolId, &Symbol)> {
+        self.symbols.iter().enumerate()
+    }
+
+    /**
+        Returns the scope with the given id.
+    */
+    #[must_use]
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id]
+    }
+
+    /**
+        Returns all resolved references to the symbol with the given id.
+    */
+    #[must_use]
+    pub fn references_to(&self, id: SymbolId) -> impl Iterator<Item = &Reference> {
+        self.references_by_target
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(|index| &self.references[*index])
+    }
+
+    /**
+        Returns all references, both resolved and unresolved, in the order they were found in.
+    */
+    pub fn references(&self) -> impl Iterator<Item = &Reference> {
+        self.references.iter()
+    }
+
+    /**
+        Finds the declaration or resolved reference at the given point.
+
+        Unresolved references are never returned, since
+        they do not have any symbol to refer to.
+    */
+    #[must_use]
+    pub fn occurrence_at(&self, point: Point) -> Option<Occurrence> {
+        let declared = self.symbols.iter().enumerate().find_map(|(id, symbol)| {
+            range_contains_point(symbol.identifier, point).then_some(Occurrence {
+                symbol: id,
+                is_declaration: true,
+                identifier: symbol.identifier,
+                range: symbol.identifier,
+            })
+        });
+
+        declared.or_else(|| {
+            self.references.iter().find_map(|reference| {
+                if !range_contains_point(reference.identifier, point) {
+                    return None;
+                }
+                Some(Occurrence {
+                    symbol: reference.target?,
+                    is_declaration: false,
+                    identifier: reference.identifier,
+                    range: if reference.kind == SymbolKind::Type {
+                        reference.reference
+                    } else {
+                        reference.identifier
+                    },
+                })
+            })
+        })
+    }
+
+    /**
+        Returns the qualified name of the symbol, such as `A.B.C`.
+    */
+    #[must_use]
+    pub fn qualified_name(&self, id: SymbolId) -> String {
+        let symbol = self.symbol(id);
+        let mut names = vec![symbol.name.as_str()];
+        let mut scope = Some(symbol.scope);
+        while let Some(current) = scope {
+            if let Some(owner) = self.scopes[current].owner {
+                names.push(self.symbols[owner].name.as_str());
+            }
+            scope = self.scopes[current].parent;
+        }
+        names.reverse();
+        names.join(".")
+    }
+
+    /**
+        Finds the declaration node for the symbol in the given source.
+
+        The source must be the same one that the model was built from.
+    */
+    #[must_use]
+    pub fn declaration_node<'a>(&self, src: &Source<'a>, id: SymbolId) -> Option<Node<'a>> {
+        let symbol = self.symbol(id);
+        let range = symbol.declaration.start_byte..symbol.declaration.end_byte;
+        let node = src
+            .root()
+            .descendant_for_byte_range(range.start, range.end)?;
+        AncestorIterator::new(node)
+            .find(|a| a.kind() == symbol.kind.node_kind() && a.byte_range() == range)
+    }
+}
+
+fn range_contains_point(range: Range, point: Point) -> bool {
+    range.start_point <= point && point <= range.end_point
+}
@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+
+use zap_language::tree_sitter::{Node, Tree};
+
+/**
+    A single Zap source file - its text, and its parsed tree.
+
+    All analysis in this crate is done on a `Source`, which may either
+    own or borrow its text, but always borrows its tree, meaning that any
+    nodes returned from the analysis live as long as the tree does.
+
+    ### Example
+
+    ```rust ignore
+    let mut parser = Parser::new();
+    parser.set_language(&zap_language::TS_LANGUAGE.into())?;
+
+    let text = "type Name = string";
+    let tree = parser.parse(text, None).unwrap();
+
+    let source = Source::new(text, &tree);
+    ```
+*/
+#[derive(Debug, Clone)]
+pub struct Source<'a> {
+    text: Cow<'a, str>,
+    root: Node<'a>,
+}
+
+impl<'a> Source<'a> {
+    /**
+        Creates a new source from the given text and its parsed tree.
+    */
+    #[must_use]
+    pub fn new(text: impl Into<Cow<'a, str>>, tree: &'a Tree) -> Self {
+        Self::from_root(text, tree.root_node())
+    }
+
+    /**
+        Creates a new source from the given text and the root node of its parsed tree.
+    */
+    #[must_use]
+    pub fn from_root(text: impl Into<Cow<'a, str>>, root: Node<'a>) -> Self {
+        Self {
+            text: text.into(),
+            root,
+        }
+    }
+
+    /**
+        Returns the full text of the source.
+    */
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /**
+        Returns the root node of the source tree.
+    */
+    #[must_use]
+    pub fn root(&self) -> Node<'a> {
+        self.root
+    }
+
+    /**
+        Returns the text for the given node, or an empty
+        string if the node is not a part of this source.
+    */
+    #[must_use]
+    pub fn node_text(&self, node: Node) -> String {
+        self.text
+            .get(node.byte_range())
+            .unwrap_or_default()
+            .to_string()
+    }
+}
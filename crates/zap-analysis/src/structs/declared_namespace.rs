@@ -1,10 +1,12 @@
-use async_language_server::{
-    server::Document,
+use zap_language::{
+    ast::{AstNode, NamespaceDecl, Visitor, visit, walk_namespace_decl},
     tree_sitter::{Node, Range},
-    tree_sitter_utils::find_ancestor,
 };
 
-use crate::utils::is_namespace;
+use crate::{
+    source::Source,
+    utils::{doc_comment, find_ancestor, is_namespace},
+};
 
 use super::ReferencedNamespace;
 
@@ -41,70 +43,62 @@ impl<'a> DeclaredNamespace<'a> {
         Constructs a new `DeclaredNamespace` from a given node,
         if the node is a valid namespace declaration.
     */
+    #[must_use]
     pub fn from_node(node: Node<'a>) -> Option<Self> {
-        if node.kind() == "namespace_declaration" {
-            let mut namespaces = Vec::new();
-            let mut namespace = node.parent();
-
-            while let Some(parent) = namespace {
-                if parent.kind() == "namespace_declaration" {
-                    namespaces.push(parent);
-                }
-                namespace = parent.parent();
-            }
+        let decl = NamespaceDecl::cast(node)?;
 
-            namespaces.reverse(); // Top-level first
+        let mut namespaces = Vec::new();
+        let mut namespace = node.parent();
 
-            Some(Self {
-                declaration: node,
-                namespaces,
-                identifier: node.child_by_field_name("name")?,
-            })
-        } else {
-            None
+        while let Some(parent) = namespace {
+            if NamespaceDecl::can_cast(parent.kind()) {
+                namespaces.push(parent);
+            }
+            namespace = parent.parent();
         }
+
+        namespaces.reverse(); // Top-level first
+
+        Some(Self {
+            declaration: node,
+            namespaces,
+            identifier: decl.name()?,
+        })
     }
 
     /**
         Finds all namespace declarations in the given document,
         within the given subtree / node.
     */
+    #[must_use]
     pub fn find_all_in(node: Node<'a>) -> Vec<Self> {
-        fn recurse<'b>(current_node: Node<'b>, results: &mut Vec<DeclaredNamespace<'b>>) {
-            match current_node.kind() {
-                "source_file" => {
-                    for child in current_node.children(&mut current_node.walk()) {
-                        recurse(child, results);
-                    }
-                }
-                "namespace_declaration" => {
-                    results.extend(DeclaredNamespace::from_node(current_node));
-                    // Also recurse into the namespace to find nested namespaces
-                    for child in current_node.children(&mut current_node.walk()) {
-                        recurse(child, results);
-                    }
-                }
-                _ => {}
+        struct Collector<'b>(Vec<DeclaredNamespace<'b>>);
+
+        impl<'b> Visitor<'b> for Collector<'b> {
+            fn visit_namespace_decl(&mut self, decl: NamespaceDecl<'b>) {
+                self.0.extend(DeclaredNamespace::from_node(decl.syntax()));
+                // Also walk into the namespace to find nested namespaces
+                walk_namespace_decl(self, decl);
             }
         }
 
-        let mut results = Vec::new();
-        recurse(node, &mut results);
-        results
+        let mut collector = Collector(Vec::new());
+        visit(&mut collector, node);
+        collector.0
     }
 
     /**
         Finds all namespace declarations in the given document.
     */
-    pub fn find_all(doc: &'a Document) -> Vec<Self> {
-        doc.node_at_root()
-            .map(Self::find_all_in)
-            .unwrap_or_default()
+    #[must_use]
+    pub fn find_all(src: &Source<'a>) -> Vec<Self> {
+        Self::find_all_in(src.root())
     }
 
     /**
         Returns the full node range for this namespace declaration.
     */
+    #[must_use]
     pub fn declaration_range(&self) -> Range {
         self.declaration.range()
     }
@@ -112,13 +106,15 @@ impl<'a> DeclaredNamespace<'a> {
     /**
         Returns the full node text for this namespace declaration.
     */
-    pub fn declaration_text(&self, doc: &Document) -> String {
-        doc.node_text(self.declaration)
+    #[must_use]
+    pub fn declaration_text(&self, src: &Source) -> String {
+        src.node_text(self.declaration)
     }
 
     /**
         Returns the identifier range for this namespace declaration.
     */
+    #[must_use]
     pub fn identifier_range(&self) -> Range {
         self.identifier.range()
     }
@@ -126,14 +122,24 @@ impl<'a> DeclaredNamespace<'a> {
     /**
         Returns the identifier text for this namespace declaration.
     */
-    pub fn identifier_text(&self, doc: &Document) -> String {
-        doc.node_text(self.identifier)
+    #[must_use]
+    pub fn identifier_text(&self, src: &Source) -> String {
+        src.node_text(self.identifier)
+    }
+
+    /**
+        Returns the doc comment for this namespace declaration, if any.
+    */
+    #[must_use]
+    pub fn doc_comment(&self, src: &Source) -> Option<String> {
+        doc_comment(src, self.declaration)
     }
 
     /**
         Returns `true` if this declaration is a part
         of the given namespace, `false` otherwise.
     */
+    #[must_use]
     pub fn is_in_namespace(&self, namespace: Node) -> bool {
         find_ancestor(self.declaration, is_namespace).is_some_and(|found| found == namespace)
     }
@@ -141,20 +147,21 @@ impl<'a> DeclaredNamespace<'a> {
     /**
         Resolves all valid references to this namespace declaration.
     */
-    pub fn resolve_references<'d: 'a>(&self, doc: &'d Document) -> Vec<ReferencedNamespace<'a>> {
-        let decl_ident = doc.node_text(self.identifier);
+    #[must_use]
+    pub fn resolve_references(&self, src: &Source) -> Vec<ReferencedNamespace<'a>> {
+        let decl_ident = src.node_text(self.identifier);
 
-        ReferencedNamespace::find_all(doc)
+        ReferencedNamespace::find_all(src)
             .into_iter()
             // First pass - filter by same final identifier (fast)
             .filter(|referenced| {
-                let ref_ident = doc.node_text(referenced.identifier);
+                let ref_ident = src.node_text(referenced.identifier);
                 ref_ident == decl_ident
             })
             // Second pass - resolve the full declaration (potentially slow)
             .filter(|referenced| {
                 referenced
-                    .resolve_declaration(doc)
+                    .resolve_declaration(src)
                     .is_some_and(|other| self.declaration == other.declaration)
             })
             .collect()
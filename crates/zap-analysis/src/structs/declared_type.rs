@@ -1,10 +1,12 @@
-use async_language_server::{
-    server::Document,
+use zap_language::{
+    ast::{AstNode, TypeDecl, Visitor, visit},
     tree_sitter::{Node, Range},
-    tree_sitter_utils::find_ancestor,
 };
 
-use crate::utils::is_namespace;
+use crate::{
+    source::Source,
+    utils::{doc_comment, find_ancestor, is_namespace},
+};
 
 use super::ReferencedType;
 
@@ -41,66 +43,60 @@ impl<'a> DeclaredType<'a> {
         Constructs a new `DeclaredType` from a given node,
         if the node is a valid type reference.
     */
+    #[must_use]
     pub fn from_node(node: Node<'a>) -> Option<Self> {
-        if node.kind() == "type_declaration" {
-            let mut namespaces = Vec::new();
-            let mut namespace = node.parent();
-
-            while let Some(parent) = namespace {
-                if parent.kind() == "namespace_declaration" {
-                    namespaces.push(parent);
-                }
-                namespace = parent.parent();
-            }
+        let decl = TypeDecl::cast(node)?;
 
-            namespaces.reverse(); // Top-level first
+        let mut namespaces = Vec::new();
+        let mut namespace = node.parent();
 
-            Some(Self {
-                declaration: node,
-                namespaces,
-                identifier: node.child_by_field_name("name")?,
-            })
-        } else {
-            None
+        while let Some(parent) = namespace {
+            if parent.kind() == "namespace_declaration" {
+                namespaces.push(parent);
+            }
+            namespace = parent.parent();
         }
+
+        namespaces.reverse(); // Top-level first
+
+        Some(Self {
+            declaration: node,
+            namespaces,
+            identifier: decl.name()?,
+        })
     }
 
     /**
         Finds all type declarations in the given document,
         within the given subtree / node.
     */
+    #[must_use]
     pub fn find_all_in(node: Node<'a>) -> Vec<Self> {
-        fn recurse<'b>(current_node: Node<'b>, results: &mut Vec<DeclaredType<'b>>) {
-            match current_node.kind() {
-                "source_file" | "namespace_declaration" => {
-                    for child in current_node.children(&mut current_node.walk()) {
-                        recurse(child, results);
-                    }
-                }
-                "type_declaration" => {
-                    results.extend(DeclaredType::from_node(current_node));
-                }
-                _ => {}
+        struct Collector<'b>(Vec<DeclaredType<'b>>);
+
+        impl<'b> Visitor<'b> for Collector<'b> {
+            fn visit_type_decl(&mut self, decl: TypeDecl<'b>) {
+                self.0.extend(DeclaredType::from_node(decl.syntax()));
             }
         }
 
-        let mut results = Vec::new();
-        recurse(node, &mut results);
-        results
+        let mut collector = Collector(Vec::new());
+        visit(&mut collector, node);
+        collector.0
     }
 
     /**
         Finds all type declarations in the given document.
     */
-    pub fn find_all(doc: &'a Document) -> Vec<Self> {
-        doc.node_at_root()
-            .map(Self::find_all_in)
-            .unwrap_or_default()
+    #[must_use]
+    pub fn find_all(src: &Source<'a>) -> Vec<Self> {
+        Self::find_all_in(src.root())
     }
 
     /**
         Returns the full node range for this type declaration.
     */
+    #[must_use]
     pub fn declaration_range(&self) -> Range {
         self.declaration.range()
     }
@@ -108,13 +104,15 @@ impl<'a> DeclaredType<'a> {
     /**
         Returns the full node text for this type declaration.
     */
-    pub fn declaration_text(&self, doc: &Document) -> String {
-        doc.node_text(self.declaration)
+    #[must_use]
+    pub fn declaration_text(&self, src: &Source) -> String {
+        src.node_text(self.declaration)
     }
 
     /**
         Returns the identifier range for this type declaration.
     */
+    #[must_use]
     pub fn identifier_range(&self) -> Range {
         self.identifier.range()
     }
@@ -122,14 +120,24 @@ impl<'a> DeclaredType<'a> {
     /**
         Returns the identifier text for this type declaration.
     */
-    pub fn identifier_text(&self, doc: &Document) -> String {
-        doc.node_text(self.identifier)
+    #[must_use]
+    pub fn identifier_text(&self, src: &Source) -> String {
+        src.node_text(self.identifier)
+    }
+
+    /**
+        Returns the doc comment for this type declaration, if any.
+    */
+    #[must_use]
+    pub fn doc_comment(&self, src: &Source) -> Option<String> {
+        doc_comment(src, self.declaration)
     }
 
     /**
         Returns `true` if this declaration is a part
         of the given namespace, `false` otherwise.
     */
+    #[must_use]
     pub fn is_in_namespace(&self, namespace: Node) -> bool {
         find_ancestor(self.declaration, is_namespace).is_some_and(|found| found == namespace)
     }
@@ -137,20 +145,21 @@ impl<'a> DeclaredType<'a> {
     /**
         Resolves all valid references to this type declaration.
     */
-    pub fn resolve_references<'d: 'a>(&self, doc: &'d Document) -> Vec<ReferencedType<'a>> {
-        let decl_ident = doc.node_text(self.identifier);
+    #[must_use]
+    pub fn resolve_references(&self, src: &Source) -> Vec<ReferencedType<'a>> {
+        let decl_ident = src.node_text(self.identifier);
 
-        ReferencedType::find_all(doc)
+        ReferencedType::find_all(src)
             .into_iter()
             // First pass - filter by same final identifier (fast)
             .filter(|referenced| {
-                let ref_ident = doc.node_text(referenced.identifier);
+                let ref_ident = src.node_text(referenced.identifier);
                 ref_ident == decl_ident
             })
             // Second pass - resolve the full declaration (potentially slow)
             .filter(|referenced| {
                 referenced
-                    .resolve_declaration(doc)
+                    .resolve_declaration(src)
                     .is_some_and(|other| self.declaration == other.declaration)
             })
             .collect()
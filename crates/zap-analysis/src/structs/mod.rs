@@ -1,5 +1,3 @@
-#![allow(dead_code)]
-
 mod declared_namespace;
 mod declared_type;
 mod referenced_namespace;
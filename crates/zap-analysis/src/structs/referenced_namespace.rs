@@ -1,10 +1,12 @@
-use async_language_server::{
-    server::Document,
+use zap_language::{
+    ast::{AstNode, NamespaceDecl, NamespacedType, Type, Visitor, visit, walk_type},
     tree_sitter::{Node, Range},
-    tree_sitter_utils::find_ancestor,
 };
 
-use crate::utils::is_namespace;
+use crate::{
+    source::Source,
+    utils::{find_ancestor, is_namespace},
+};
 
 use super::DeclaredNamespace;
 
@@ -38,14 +40,14 @@ impl<'a> ReferencedNamespace<'a> {
         Constructs a new `ReferencedNamespace` from a given node,
         if the node is a valid namespace reference.
     */
+    #[must_use]
     pub fn from_node(node: Node<'a>) -> Option<Self> {
         // Check if this identifier is part of a namespaced type
-        let namespaced_type = find_ancestor(node, |n| n.kind() == "namespaced_type")?;
+        let namespaced_type = find_ancestor(node, |n| NamespacedType::can_cast(n.kind()))
+            .and_then(NamespacedType::cast)?;
 
         // Get all namespace identifiers from the namespaced type
-        let all_namespaces: Vec<Node> = namespaced_type
-            .children_by_field_name("namespace", &mut namespaced_type.walk())
-            .collect();
+        let all_namespaces = namespaced_type.namespaces();
 
         // Find the position of our node in the namespace chain
         let node_position = all_namespaces.iter().position(|&n| n == node)?;
@@ -54,7 +56,7 @@ impl<'a> ReferencedNamespace<'a> {
         let parent_namespaces = all_namespaces[..node_position].to_vec();
 
         Some(Self {
-            reference: namespaced_type,
+            reference: namespaced_type.syntax(),
             namespaces: parent_namespaces,
             identifier: node,
         })
@@ -64,42 +66,41 @@ impl<'a> ReferencedNamespace<'a> {
         Finds all namespace references in the given document,
         within the given subtree / node.
     */
+    #[must_use]
     pub fn find_all_in(node: Node<'a>) -> Vec<Self> {
-        fn recurse<'b>(current_node: Node<'b>, results: &mut Vec<ReferencedNamespace<'b>>) {
-            match current_node.kind() {
-                "namespaced_type" => {
+        struct Collector<'b>(Vec<ReferencedNamespace<'b>>);
+
+        impl<'b> Visitor<'b> for Collector<'b> {
+            fn visit_type(&mut self, typ: Type<'b>) {
+                if let Type::Namespaced(namespaced) = typ {
                     // Add all namespace identifiers from this namespaced type
-                    for namespace_node in
-                        current_node.children_by_field_name("namespace", &mut current_node.walk())
-                    {
-                        results.extend(ReferencedNamespace::from_node(namespace_node));
-                    }
-                }
-                _ => {
-                    for child in current_node.children(&mut current_node.walk()) {
-                        recurse(child, results);
+                    for namespace_node in namespaced.namespaces() {
+                        self.0
+                            .extend(ReferencedNamespace::from_node(namespace_node));
                     }
+                } else {
+                    walk_type(self, typ);
                 }
             }
         }
 
-        let mut results = Vec::new();
-        recurse(node, &mut results);
-        results
+        let mut collector = Collector(Vec::new());
+        visit(&mut collector, node);
+        collector.0
     }
 
     /**
         Finds all namespace references in the given document.
     */
-    pub fn find_all(doc: &'a Document) -> Vec<Self> {
-        doc.node_at_root()
-            .map(Self::find_all_in)
-            .unwrap_or_default()
+    #[must_use]
+    pub fn find_all(src: &Source<'a>) -> Vec<Self> {
+        Self::find_all_in(src.root())
     }
 
     /**
         Returns the full node range for this namespace reference.
     */
+    #[must_use]
     pub fn reference_range(&self) -> Range {
         self.reference.range()
     }
@@ -107,13 +108,15 @@ impl<'a> ReferencedNamespace<'a> {
     /**
         Returns the full node text for this namespace reference.
     */
-    pub fn reference_text(&self, doc: &Document) -> String {
-        doc.node_text(self.reference)
+    #[must_use]
+    pub fn reference_text(&self, src: &Source) -> String {
+        src.node_text(self.reference)
     }
 
     /**
         Returns the identifier range for this namespace reference.
     */
+    #[must_use]
     pub fn identifier_range(&self) -> Range {
         self.identifier.range()
     }
@@ -121,14 +124,27 @@ impl<'a> ReferencedNamespace<'a> {
     /**
         Returns the identifier text for this namespace reference.
     */
-    pub fn identifier_text(&self, doc: &Document) -> String {
-        doc.node_text(self.identifier)
+    #[must_use]
+    pub fn identifier_text(&self, src: &Source) -> String {
+        src.node_text(self.identifier)
+    }
+
+    /**
+        Returns the text of each namespace walked by this namespace reference, outermost first.
+    */
+    #[must_use]
+    pub fn namespace_texts(&self, src: &Source) -> Vec<String> {
+        self.namespaces
+            .iter()
+            .map(|ns| src.node_text(*ns))
+            .collect()
     }
 
     /**
         Returns `true` if this reference is a part
         of the given namespace, `false` otherwise.
     */
+    #[must_use]
     pub fn is_in_namespace(&self, namespace: Node) -> bool {
         find_ancestor(self.reference, is_namespace).is_some_and(|found| found == namespace)
     }
@@ -136,26 +152,24 @@ impl<'a> ReferencedNamespace<'a> {
     /**
         Finds the declaration, if any, for this namespace reference.
     */
-    pub fn resolve_declaration<'d: 'a>(&self, doc: &'d Document) -> Option<DeclaredNamespace<'a>> {
+    #[must_use]
+    pub fn resolve_declaration(&self, src: &Source) -> Option<DeclaredNamespace<'a>> {
         // 1. First, we must find the correct root namespace to search in
         let mut namespace = find_ancestor(self.reference, is_namespace)?;
 
         // 2. Next, if our namespace reference has parent namespaces,
         //    we should walk all of those, or return None if any is missing
         'outer: for &parent_ident_node in &self.namespaces {
-            let parent_ident_text = doc.node_text(parent_ident_node);
+            let parent_ident_text = src.node_text(parent_ident_node);
 
             let mut cursor = namespace.walk();
             for child in namespace.children(&mut cursor) {
-                if child.kind() == "namespace_declaration" {
-                    let name_node = child
-                        .child_by_field_name("name")
-                        .expect("valid namespace declaration");
-                    let name_text = doc.node_text(name_node);
-                    if name_text == parent_ident_text {
-                        namespace = child;
-                        continue 'outer;
-                    }
+                let Some(name_node) = NamespaceDecl::cast(child).and_then(|d| d.name()) else {
+                    continue;
+                };
+                if src.node_text(name_node) == parent_ident_text {
+                    namespace = child;
+                    continue 'outer;
                 }
             }
 
@@ -163,18 +177,15 @@ impl<'a> ReferencedNamespace<'a> {
         }
 
         // 3. We should now be in the correct namespace, find the namespace declaration
-        let ident_referenced = self.identifier_text(doc);
+        let ident_referenced = self.identifier_text(src);
 
         let mut cursor = namespace.walk();
         for child in namespace.children(&mut cursor) {
-            if child.kind() == "namespace_declaration" {
-                let name_node = child
-                    .child_by_field_name("name")
-                    .expect("valid namespace declaration");
-                let name_text = doc.node_text(name_node);
-                if name_text == ident_referenced {
-                    return DeclaredNamespace::from_node(child);
-                }
+            let Some(name_node) = NamespaceDecl::cast(child).and_then(|d| d.name()) else {
+                continue;
+            };
+            if src.node_text(name_node) == ident_referenced {
+                return DeclaredNamespace::from_node(child);
             }
         }
 
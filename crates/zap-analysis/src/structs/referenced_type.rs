@@ -1,10 +1,12 @@
-use async_language_server::{
-    server::Document,
+use zap_language::{
+    ast::{AstNode, NamespaceDecl, NamespacedType, Type, TypeDecl, Visitor, visit, walk_type},
     tree_sitter::{Node, Range},
-    tree_sitter_utils::find_ancestor,
 };
 
-use crate::utils::{is_namespace, is_type_reference};
+use crate::{
+    source::Source,
+    utils::{find_ancestor, is_namespace, is_type_reference},
+};
 
 use super::DeclaredType;
 
@@ -37,14 +39,13 @@ impl<'a> ReferencedType<'a> {
         Constructs a new `ReferencedType` from a given node,
         if the node is a valid type reference.
     */
+    #[must_use]
     pub fn from_node(node: Node<'a>) -> Option<Self> {
-        if node.kind() == "namespaced_type" {
+        if let Some(typ) = NamespacedType::cast(node) {
             Some(Self {
                 reference: node,
-                namespaces: node
-                    .children_by_field_name("namespace", &mut node.walk())
-                    .collect(),
-                identifier: node.child_by_field_name("type")?,
+                namespaces: typ.namespaces(),
+                identifier: typ.name()?,
             })
         } else if is_type_reference(node) {
             Some(Self {
@@ -61,37 +62,38 @@ impl<'a> ReferencedType<'a> {
         Finds all type references in the given document,
         within the given subtree / node.
     */
+    #[must_use]
     pub fn find_all_in(node: Node<'a>) -> Vec<Self> {
-        fn recurse<'b>(current_node: Node<'b>, results: &mut Vec<ReferencedType<'b>>) {
-            match current_node.kind() {
-                "namespaced_type" | "identifier" => {
-                    results.extend(ReferencedType::from_node(current_node));
-                }
-                _ => {
-                    for child in current_node.children(&mut current_node.walk()) {
-                        recurse(child, results);
+        struct Collector<'b>(Vec<ReferencedType<'b>>);
+
+        impl<'b> Visitor<'b> for Collector<'b> {
+            fn visit_type(&mut self, typ: Type<'b>) {
+                match typ {
+                    Type::Reference(_) | Type::Namespaced(_) => {
+                        self.0.extend(ReferencedType::from_node(typ.syntax()));
                     }
+                    _ => walk_type(self, typ),
                 }
             }
         }
 
-        let mut results = Vec::new();
-        recurse(node, &mut results);
-        results
+        let mut collector = Collector(Vec::new());
+        visit(&mut collector, node);
+        collector.0
     }
 
     /**
         Finds all type references in the given document.
     */
-    pub fn find_all(doc: &'a Document) -> Vec<Self> {
-        doc.node_at_root()
-            .map(Self::find_all_in)
-            .unwrap_or_default()
+    #[must_use]
+    pub fn find_all(src: &Source<'a>) -> Vec<Self> {
+        Self::find_all_in(src.root())
     }
 
     /**
         Returns the full node range for this type reference.
     */
+    #[must_use]
     pub fn reference_range(&self) -> Range {
         self.reference.range()
     }
@@ -99,13 +101,15 @@ impl<'a> ReferencedType<'a> {
     /**
         Returns the full node text for this type reference.
     */
-    pub fn reference_text(&self, doc: &Document) -> String {
-        doc.node_text(self.reference)
+    #[must_use]
+    pub fn reference_text(&self, src: &Source) -> String {
+        src.node_text(self.reference)
     }
 
     /**
         Returns the identifier range for this type reference.
     */
+    #[must_use]
     pub fn identifier_range(&self) -> Range {
         self.identifier.range()
     }
@@ -113,14 +117,27 @@ impl<'a> ReferencedType<'a> {
     /**
         Returns the identifier text for this type reference.
     */
-    pub fn identifier_text(&self, doc: &Document) -> String {
-        doc.node_text(self.identifier)
+    #[must_use]
+    pub fn identifier_text(&self, src: &Source) -> String {
+        src.node_text(self.identifier)
+    }
+
+    /**
+        Returns the text of each namespace walked by this type reference, outermost first.
+    */
+    #[must_use]
+    pub fn namespace_texts(&self, src: &Source) -> Vec<String> {
+        self.namespaces
+            .iter()
+            .map(|ns| src.node_text(*ns))
+            .collect()
     }
 
     /**
         Returns `true` if this reference is a part
         of the given namespace, `false` otherwise.
     */
+    #[must_use]
     pub fn is_in_namespace(&self, namespace: Node) -> bool {
         find_ancestor(self.reference, is_namespace).is_some_and(|found| found == namespace)
     }
@@ -130,11 +147,8 @@ impl<'a> ReferencedType<'a> {
 
         An optional `limit` may be provided to only walk a certain number of namespaces.
     */
-    pub fn resolve_namespace<'d: 'a>(
-        &self,
-        doc: &'d Document,
-        limit: Option<usize>,
-    ) -> Option<Node<'a>> {
+    #[must_use]
+    pub fn resolve_namespace(&self, src: &Source, limit: Option<usize>) -> Option<Node<'a>> {
         let mut namespace = find_ancestor(self.reference, is_namespace)?;
         let mut current = 0;
 
@@ -143,20 +157,17 @@ impl<'a> ReferencedType<'a> {
                 break;
             }
 
-            let ident_text = doc.node_text(ident_node);
+            let ident_text = src.node_text(ident_node);
 
             let mut cursor = namespace.walk();
             for child in namespace.children(&mut cursor) {
-                if child.kind() == "namespace_declaration" {
-                    let name_node = child
-                        .child_by_field_name("name")
-                        .expect("valid namespace declaration");
-                    let name_text = doc.node_text(name_node);
-                    if name_text == ident_text {
-                        namespace = child;
-                        current += 1;
-                        continue 'outer;
-                    }
+                let Some(name_node) = NamespaceDecl::cast(child).and_then(|d| d.name()) else {
+                    continue;
+                };
+                if src.node_text(name_node) == ident_text {
+                    namespace = child;
+                    current += 1;
+                    continue 'outer;
                 }
             }
 
@@ -169,22 +180,19 @@ impl<'a> ReferencedType<'a> {
     /**
         Finds the declaration, if any, for this type reference.
     */
-    pub fn resolve_declaration<'d: 'a>(&self, doc: &'d Document) -> Option<DeclaredType<'a>> {
-        let namespace = self.resolve_namespace(doc, None)?;
+    #[must_use]
+    pub fn resolve_declaration(&self, src: &Source) -> Option<DeclaredType<'a>> {
+        let namespace = self.resolve_namespace(src, None)?;
 
-        let ident_referenced = self.identifier_text(doc);
+        let ident_referenced = self.identifier_text(src);
 
         let mut cursor = namespace.walk();
         for child in namespace.children(&mut cursor) {
-            if child.kind() == "type_declaration" {
-                let name_node = child
-                    .child_by_field_name("name")
-                    .expect("valid type declaration");
-                let name_text = doc.node_text(name_node);
-                if name_text == ident_referenced {
-                    let res = DeclaredType::from_node(child);
-                    return res;
-                }
+            let Some(name_node) = TypeDecl::cast(child).and_then(|d| d.name()) else {
+                continue;
+            };
+            if src.node_text(name_node) == ident_referenced {
+                return DeclaredType::from_node(child);
             }
         }
 
@@ -0,0 +1,205 @@
+use std::ops::Range as ByteRange;
+
+use zap_language::{tree_sitter::Node, tree_sitter_utils::AncestorIterator};
+
+use crate::{source::Source, structs::ReferencedType};
+
+/**
+    Checks if the given node is a namespace declaration, or the root / source file.
+*/
+#[must_use]
+pub fn is_namespace(node: Node) -> bool {
+    matches!(node.kind(), "source_file" | "namespace_declaration")
+}
+
+/**
+    Checks if the given node is a type, primitive or reference.
+*/
+#[must_use]
+pub fn is_type(node: Node) -> bool {
+    is_type_primitive(node) || is_type_reference(node)
+}
+
+/**
+    Checks if the given node is a primitive type.
+*/
+#[must_use]
+pub fn is_type_primitive(node: Node) -> bool {
+    matches!(node.kind(), "primitive_type")
+}
+
+/**
+    Checks if the given identifier node is a type reference.
+
+    Note that this does not check if the node is a **valid** type
+    reference, only that it is *trying* to reference some type.
+*/
+#[must_use]
+pub fn is_type_reference(node: Node) -> bool {
+    // Type references are always classified as "identifier"
+    let is_ident_and_matches_this_node =
+        |child: Node| child == node && child.kind() == "identifier";
+
+    // All type references have some kind of parent node
+    let Some(p) = node.parent() else {
+        return false;
+    };
+
+    match p.kind() {
+        // These all have an inner "type" field
+        "namespaced_type" | "optional_type" | "property" | "set_type" => p
+            .child_by_field_name("type")
+            .is_some_and(is_ident_and_matches_this_node),
+
+        // Maps have two inner fields "key_type" and "value_type"
+        "map_type" => {
+            p.child_by_field_name("key_type")
+                .is_some_and(is_ident_and_matches_this_node)
+                || p.child_by_field_name("value_type")
+                    .is_some_and(is_ident_and_matches_this_node)
+        }
+
+        // Type declarations have an inner "value" field
+        "type_declaration" => p
+            .child_by_field_name("value")
+            .is_some_and(is_ident_and_matches_this_node),
+
+        // The data / args / rets fields have a single child that
+        // is not a keyword and is either a primitive or identifier
+        "event_data_field" | "function_args_field" | "function_rets_field" => {
+            find_child(p, is_ident_and_matches_this_node).is_some()
+        }
+
+        // The encompassing "type" node has at least a single inner
+        // child, unnamed, which is the actual type contents of it
+        "type" => p.child(0).is_some_and(is_ident_and_matches_this_node),
+
+        // Nothing else can be a type reference according to grammar
+        _ => false,
+    }
+}
+
+/**
+    Returns the Markdown text of all `---` doc comments directly above
+    the given declaration, with the comment markers stripped, if any.
+
+    Regular comments and blank lines separate doc comments from
+    declarations, in which case they are not attached to them.
+*/
+#[must_use]
+pub fn doc_comment(src: &Source, decl: Node) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut start_row = decl.range().start_point.row;
+    let mut current = decl.prev_sibling();
+    while let Some(prev) = current {
+        if prev.kind() != "doc_comment" || prev.range().end_point.row + 1 < start_row {
+            break;
+        }
+        start_row = prev.range().start_point.row;
+        lines.push(src.node_text(prev));
+        current = prev.prev_sibling();
+    }
+    lines.reverse();
+
+    let text = lines
+        .iter()
+        .flat_map(|comment| comment.lines())
+        .map(|line| {
+            let line = line.trim().trim_start_matches('-');
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/**
+    Returns the names of all namespaces that must be walked to get
+    from the namespace `from` to the namespace `to`, outermost first.
+
+    Returns `None` if `to` is not inside of `from`, since
+    type references can only ever walk into namespaces.
+*/
+#[must_use]
+pub fn namespace_path(src: &Source, from: Node, to: Node) -> Option<Vec<String>> {
+    let mut path = Vec::new();
+    for ancestor in AncestorIterator::new(to) {
+        if ancestor == from {
+            path.reverse();
+            return Some(path);
+        }
+        if ancestor.kind() == "namespace_declaration" {
+            path.push(src.node_text(ancestor.child_by_field_name("name")?));
+        }
+    }
+    None
+}
+
+/**
+    Returns the text within the given byte range, with all
+    the given (non-overlapping) replacements applied to it.
+*/
+#[must_use]
+pub fn replace_bytes(
+    text: &[u8],
+    range: ByteRange<usize>,
+    replacements: &[(ByteRange<usize>, String)],
+) -> String {
+    let mut sorted = replacements.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|(r, _)| r.start);
+
+    let mut result = String::new();
+    let mut cursor = range.start;
+    for (replaced, new_text) in sorted {
+        result.push_str(&String::from_utf8_lossy(&text[cursor..replaced.start]));
+        result.push_str(new_text);
+        cursor = replaced.end;
+    }
+    result.push_str(&String::from_utf8_lossy(&text[cursor..range.end]));
+    result
+}
+
+/**
+    Returns the text of the given type, with all type references inside
+    of it qualified such that they resolve from the given namespace.
+
+    Returns `None` if any of the referenced types are not reachable from the namespace.
+*/
+#[must_use]
+pub fn qualified_type_text(src: &Source, value: Node, namespace: Node) -> Option<String> {
+    let mut replacements = Vec::new();
+    for inner in ReferencedType::find_all_in(value) {
+        // Unresolved references are broken already, keep them as they are
+        let Some(target) = inner.resolve_declaration(src) else {
+            continue;
+        };
+        let target_namespace = find_ancestor(*target.as_ref(), is_namespace)?;
+
+        let mut path = namespace_path(src, namespace, target_namespace)?;
+        path.push(target.identifier_text(src));
+        replacements.push((inner.as_ref().byte_range(), path.join(".")));
+    }
+
+    Some(replace_bytes(
+        src.text().as_bytes(),
+        value.byte_range(),
+        &replacements,
+    ))
+}
+
+/**
+    Finds the nearest ancestor of the given node, not including
+    the node itself, that matches the given predicate.
+*/
+pub(crate) fn find_ancestor<'a>(
+    node: Node<'a>,
+    predicate: impl Fn(Node) -> bool,
+) -> Option<Node<'a>> {
+    AncestorIterator::new(node).skip(1).find(|a| predicate(*a))
+}
@@ -1,64 +1,67 @@
 use std::fmt;
 
-use zap_language::{tree_sitter::Node, tree_sitter_utils::is_known_node};
-
-use crate::{format_node, format_plain, result::Result, state::State, types::format_type};
+use zap_language::{
+    ast::{AstNode, Declaration, FieldDecl},
+    tree_sitter::Node,
+    tree_sitter_utils::is_known_node,
+};
+
+use crate::{
+    format_node, format_plain,
+    result::{OptionExt, Result},
+    state::State,
+    types::format_type,
+};
 
 pub(crate) fn format_declaration(
     writer: &mut impl fmt::Write,
     state: &mut State,
     node: Node,
 ) -> Result {
-    format_declaration_pre(writer, state, node)?;
+    let decl = Declaration::cast(node).or_unexpected(node)?;
+    format_declaration_pre(writer, state, decl)?;
 
-    if node.kind() == "option_declaration" {
-        let value = node.child(3).expect("valid option declaration");
+    if let Declaration::Option(option) = decl {
+        let value = option.value().or_missing(node, "option value")?;
         let value = state.text(value);
         write!(writer, "{value}")?;
-    } else if node.kind() == "type_declaration" {
-        if let Some(value) = node.child(3) {
-            format_type(writer, state, value)?;
+    } else if let Declaration::Type(typ) = decl {
+        if let Some(value) = typ.value() {
+            format_type(writer, state, value.syntax())?;
         }
-    } else if matches!(node.kind(), "event_declaration" | "function_declaration") {
+    } else if matches!(decl, Declaration::Event(_) | Declaration::Funct(_)) {
         writeln!(writer, "{{")?;
 
         state.increase_depth();
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor).skip(2) {
-            match child.kind() {
-                "event_from_field"
-                | "event_type_field"
-                | "event_call_field"
-                | "event_data_field"
-                | "function_call_field"
-                | "function_args_field"
-                | "function_rets_field" => {
-                    write!(writer, "{}", state.indent())?;
-                    format_declaration_field(writer, state, child)?;
-                }
-                _ if is_known_node(child) => {
-                    write!(writer, "{}", state.indent())?;
-                    format_node(writer, state, child)?;
-                }
-                _ => {}
+            if let Some(field) = FieldDecl::cast(child) {
+                write!(writer, "{}", state.indent())?;
+                format_declaration_field(writer, state, field)?;
+            } else if is_known_node(child) {
+                write!(writer, "{}", state.indent())?;
+                format_node(writer, state, child)?;
             }
         }
 
         state.decrease_depth();
 
         write!(writer, "{}}}", state.indent())?;
-    } else if node.kind() == "namespace_declaration" {
+    } else if let Declaration::Namespace(namespace) = decl {
         writeln!(writer, "{{")?;
 
         state.increase_depth();
 
+        let open_brace = namespace.open_brace();
         let mut cursor = node.walk();
-        let mut last_end_row = node
-            .child(3)
+        let mut last_end_row = open_brace
             .map(|n| n.range().end_point.row)
             .unwrap_or_default();
-        for child in node.children(&mut cursor).skip(3) {
+        for child in node
+            .children(&mut cursor)
+            .skip_while(|child| open_brace.is_some_and(|brace| *child != brace))
+        {
             if matches!(child.kind(), "=" | "{" | "}") {
                 continue;
             }
@@ -81,13 +84,11 @@ pub(crate) fn format_declaration(
         // NOTE: We should preserve a single empty line before the closing
         // brace if a user has added one, for consistency, since we also
         // preserve a single opening empty line in our main loop above
-        if let Some(end_bracket) = node.child(node.child_count() - 1) {
-            if end_bracket.kind() == "}" {
-                let end_bracket_start = end_bracket.range().start_point.row;
-                let has_blank_line = last_end_row < end_bracket_start.saturating_sub(1);
-                if has_blank_line {
-                    writeln!(writer)?;
-                }
+        if let Some(end_bracket) = namespace.close_brace() {
+            let end_bracket_start = end_bracket.range().start_point.row;
+            let has_blank_line = last_end_row < end_bracket_start.saturating_sub(1);
+            if has_blank_line {
+                writeln!(writer)?;
             }
         }
 
@@ -99,9 +100,14 @@ pub(crate) fn format_declaration(
     Ok(())
 }
 
-fn format_declaration_pre(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let keyword = node.child(0).expect("valid declaration");
-    let identifier = node.child(1).expect("valid declaration");
+fn format_declaration_pre(
+    writer: &mut impl fmt::Write,
+    state: &mut State,
+    decl: Declaration,
+) -> Result {
+    let node = decl.syntax();
+    let keyword = decl.keyword().or_missing(node, "declaration keyword")?;
+    let identifier = decl.name().or_missing(node, "declaration name")?;
 
     write!(
         writer,
@@ -113,15 +119,14 @@ fn format_declaration_pre(writer: &mut impl fmt::Write, state: &mut State, node:
     Ok(())
 }
 
-fn format_declaration_field(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let key = node
-        .kind()
-        .trim_start_matches("event_")
-        .trim_start_matches("function_")
-        .trim_end_matches("_field");
-    write!(writer, "{key}: ")?;
+fn format_declaration_field(
+    writer: &mut impl fmt::Write,
+    state: &mut State,
+    field: FieldDecl,
+) -> Result {
+    write!(writer, "{}: ", field.key())?;
 
-    let value = node.child(2).expect("valid event or function field");
+    let value = field.value().or_missing(field.syntax(), "field value")?;
     if is_known_node(value) {
         format_node(writer, state, value)?;
     } else {
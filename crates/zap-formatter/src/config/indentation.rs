@@ -12,7 +12,7 @@ pub enum Indentation {
 
 impl Indentation {
     #[must_use]
-    pub(crate) const fn as_str(self) -> &'static str {
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Tabs => "\t",
             Self::Spaces => "    ",
@@ -29,6 +29,11 @@ impl<'a> Config<'a> {
         self.indentation = indentation;
         self
     }
+
+    #[must_use]
+    pub fn indentation(&self) -> Indentation {
+        self.indentation
+    }
 }
 
 impl Default for Config<'_> {
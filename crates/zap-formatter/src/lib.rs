@@ -11,6 +11,7 @@ mod types;
 use self::basic::{
     comments::format_comment, declarations::format_declaration, plain::format_plain,
 };
+use self::result::catch_panic;
 use self::state::State;
 use self::types::format_type;
 
@@ -28,12 +29,15 @@ pub use self::result::{Error, Result};
     # Errors
 
     - If the given document tree contains any error node
-    - If the formatter encounters an internal error / bug
+    - If the given document tree is missing expected nodes
+    - If the formatter encounters an internal error / bug, including panics
 */
 pub fn format_root(writer: &mut impl fmt::Write, config: Config, root: Node) -> Result {
-    let mut state = State::new(config, 0);
-    format_node(writer, &mut state, root)?;
-    Ok(())
+    catch_panic(|| {
+        let mut state = State::new(config, 0);
+        format_node(writer, &mut state, root)?;
+        Ok(())
+    })
 }
 
 /**
@@ -42,43 +46,46 @@ pub fn format_root(writer: &mut impl fmt::Write, config: Config, root: Node) ->
     # Errors
 
     - If the given document tree contains any error node
-    - If the formatter encounters an internal error / bug
+    - If the given document tree is missing expected nodes
+    - If the formatter encounters an internal error / bug, including panics
 */
 pub fn format_document(writer: &mut impl fmt::Write, config: Config, root: Node) -> Result {
     use zap_language::tree_sitter_utils::DepthFirstNodeIterator;
 
-    for node in DepthFirstNodeIterator::new(root) {
-        if node.kind() == "ERROR" {
-            let start = node.range().start_point;
-            return Err(Error::Node(start.row, start.column));
+    catch_panic(|| {
+        for node in DepthFirstNodeIterator::new(root) {
+            if node.kind() == "ERROR" {
+                let start = node.range().start_point;
+                return Err(Error::Node(start.row, start.column));
+            }
         }
-    }
 
-    let mut state = State::new(config, 0);
-    let mut cursor = root.walk();
+        let mut state = State::new(config, 0);
+        let mut cursor = root.walk();
 
-    let mut last_end_row = 0;
-    for child in root.children(&mut cursor) {
-        let this_row_start = child.range().start_point.row;
-        let this_row_end = child.range().end_point.row;
+        let mut last_end_row = 0;
+        for child in root.children(&mut cursor) {
+            let this_row_start = child.range().start_point.row;
+            let this_row_end = child.range().end_point.row;
 
-        let has_blank_line = last_end_row < this_row_start.saturating_sub(1);
-        last_end_row = this_row_end;
+            let has_blank_line = last_end_row < this_row_start.saturating_sub(1);
+            last_end_row = this_row_end;
 
-        if has_blank_line {
-            writeln!(writer)?;
-        }
+            if has_blank_line {
+                writeln!(writer)?;
+            }
 
-        if child.kind() == "comment" {
-            format_comment(writer, &mut state, child)?;
-        } else {
-            format_node(writer, &mut state, child)?;
-        }
+            if child.kind() == "comment" {
+                format_comment(writer, &mut state, child)?;
+            } else {
+                format_node(writer, &mut state, child)?;
+            }
 
-        writeln!(writer)?;
-    }
+            writeln!(writer)?;
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
 
 fn format_node(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
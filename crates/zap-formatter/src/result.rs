@@ -1,5 +1,7 @@
 use std::fmt;
 
+use zap_language::tree_sitter::Node;
+
 /**
     An error that can occur while formatting a Zap document.
 */
@@ -7,11 +9,79 @@ use std::fmt;
 pub enum Error {
     #[error("encountered error node / invalid syntax at {0}:{1}")]
     Node(usize, usize),
+    #[error("missing {expected} in `{kind}` node at {row}:{column}")]
+    MissingChild {
+        kind: &'static str,
+        expected: &'static str,
+        row: usize,
+        column: usize,
+    },
+    #[error("encountered unexpected `{kind}` node at {row}:{column}")]
+    UnexpectedNode {
+        kind: &'static str,
+        row: usize,
+        column: usize,
+    },
     #[error("encountered internal error during formatting")]
     Fmt(#[from] fmt::Error),
+    #[error("formatter panicked: {0}")]
+    Panic(String),
+}
+
+impl Error {
+    /**
+        Creates an error for a node that is missing an expected child node.
+    */
+    pub(crate) fn missing_child(node: Node, expected: &'static str) -> Self {
+        let start = node.start_position();
+        Self::MissingChild {
+            kind: node.kind(),
+            expected,
+            row: start.row,
+            column: start.column,
+        }
+    }
+
+    /**
+        Creates an error for a node that was not of the kind the formatter expected.
+    */
+    pub(crate) fn unexpected_node(node: Node) -> Self {
+        let start = node.start_position();
+        Self::UnexpectedNode {
+            kind: node.kind(),
+            row: start.row,
+            column: start.column,
+        }
+    }
 }
 
 /**
     Type alias for results that may return a Zap formatting error.
 */
 pub type Result<T = ()> = std::result::Result<T, Error>;
+
+/**
+    Extension trait for turning missing typed node accessors into formatting errors.
+*/
+pub(crate) trait OptionExt<T> {
+    fn or_missing(self, node: Node, expected: &'static str) -> Result<T>;
+    fn or_unexpected(self, node: Node) -> Result<T>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn or_missing(self, node: Node, expected: &'static str) -> Result<T> {
+        self.ok_or_else(|| Error::missing_child(node, expected))
+    }
+
+    fn or_unexpected(self, node: Node) -> Result<T> {
+        self.ok_or_else(|| Error::unexpected_node(node))
+    }
+}
+
+/**
+    Runs the given formatting function, turning any panic
+    inside of it into an error instead of unwinding further.
+*/
+pub(crate) fn catch_panic(f: impl FnOnce() -> Result) -> Result {
+    zap_language::guard::catch_panic(f).unwrap_or_else(|message| Err(Error::Panic(message)))
+}
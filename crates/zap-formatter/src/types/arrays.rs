@@ -2,12 +2,15 @@ use std::fmt;
 
 use zap_language::tree_sitter::Node;
 
-use crate::{result::Result, state::State};
+use crate::{
+    result::{OptionExt, Result},
+    state::State,
+};
 
 pub(crate) fn format_array(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
     if node.kind() == "array" {
         // Arrays have a single inner variant
-        let inner = node.child(0).expect("valid array");
+        let inner = node.child(0).or_missing(node, "inner array")?;
         format_array(writer, state, inner)?;
     } else {
         // We are inside an inner array
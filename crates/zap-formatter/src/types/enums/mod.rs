@@ -2,11 +2,15 @@ use std::collections::HashSet;
 use std::fmt;
 
 use zap_language::{
+    ast::{AstNode, EnumType, EnumVariant, Property},
     tree_sitter::Node,
     tree_sitter_utils::{is_known_node, is_type_empty},
 };
 
-use crate::{result::Result, state::State};
+use crate::{
+    result::{OptionExt, Result},
+    state::State,
+};
 
 mod tagged;
 mod untagged;
@@ -15,7 +19,8 @@ use self::tagged::{format_tagged_compact, format_tagged_multiline};
 use self::untagged::{format_untagged_grid, format_untagged_line, format_untagged_multiline};
 
 pub(crate) fn format_enum(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let tag = node.child_by_field_name("tag").map(|t| state.text(t));
+    let enum_type = EnumType::cast(node).or_unexpected(node)?;
+    let tag = enum_type.tag().map(|t| state.text(t));
     if is_type_empty(node, Some(1)) {
         // No contents, single line with no space inbetween braces
         if let Some(tag) = tag {
@@ -34,8 +39,8 @@ pub(crate) fn format_enum(writer: &mut impl fmt::Write, state: &mut State, node:
 
         let mut cursor = node.walk();
         'outer: for child in node.children(&mut cursor).skip(2) {
-            if child.kind() == "enum_variant" {
-                let ident = child.child(0).expect("valid enum variant");
+            if let Some(variant) = EnumVariant::cast(child) {
+                let ident = variant.name().or_missing(child, "enum variant name")?;
                 let ident = state.text(ident);
 
                 variant_len_min = variant_len_min.min(ident.len());
@@ -43,8 +48,10 @@ pub(crate) fn format_enum(writer: &mut impl fmt::Write, state: &mut State, node:
 
                 let mut child_cursor = child.walk();
                 for descendant in child.children(&mut child_cursor).skip(2) {
-                    if descendant.kind() == "property" {
-                        let key = descendant.child(0).expect("valid enum variant field");
+                    if let Some(property) = Property::cast(descendant) {
+                        let key = property
+                            .name()
+                            .or_missing(descendant, "enum variant field name")?;
                         variant_field_names.insert(state.text(key));
                     } else if is_known_node(descendant) {
                         variant_simple = false;
@@ -75,8 +82,8 @@ pub(crate) fn format_enum(writer: &mut impl fmt::Write, state: &mut State, node:
         let mut identifiers = Vec::new();
         let mut all_children_are_variants = true;
         for child in node.children(&mut cursor).skip(1) {
-            if child.kind() == "enum_variant" {
-                let ident = child.child(0).expect("valid enum variant");
+            if let Some(variant) = EnumVariant::cast(child) {
+                let ident = variant.name().or_missing(child, "enum variant name")?;
                 identifiers.push(state.text(ident).to_string());
             } else if is_known_node(child) {
                 all_children_are_variants = false;
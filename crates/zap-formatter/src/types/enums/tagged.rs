@@ -1,11 +1,16 @@
 use std::fmt;
 
 use zap_language::{
+    ast::{AstNode, EnumType, EnumVariant, Property},
     tree_sitter::Node,
     tree_sitter_utils::{is_comment_node, is_known_node, is_type_empty},
 };
 
-use crate::{format_node, result::Result, state::State};
+use crate::{
+    format_node,
+    result::{Error, OptionExt, Result},
+    state::State,
+};
 
 /**
     Formats a tagged enum using compact formatting.
@@ -26,7 +31,10 @@ pub(super) fn format_tagged_compact(
     node: Node,
     variant_len_max: usize,
 ) -> Result {
-    let tag = node.child_by_field_name("tag").unwrap();
+    let tag = EnumType::cast(node)
+        .or_unexpected(node)?
+        .tag()
+        .or_missing(node, "enum tag")?;
     let tag = state.text(tag);
 
     writeln!(writer, "enum {tag} {{")?;
@@ -35,12 +43,13 @@ pub(super) fn format_tagged_compact(
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        if child.kind() == "enum_variant" {
+        if let Some(variant) = EnumVariant::cast(child) {
             write!(writer, "{}", state.indent())?;
-            format_variant_compact(writer, state, child, variant_len_max)?;
+            format_variant_compact(writer, state, variant, variant_len_max)?;
             writeln!(writer, ",")?;
         } else if is_known_node(child) {
-            unreachable!("not a compact tagged enum (??)")
+            // Compact tagged enums may only contain variants
+            return Err(Error::unexpected_node(child));
         }
     }
 
@@ -54,26 +63,26 @@ pub(super) fn format_tagged_compact(
 fn format_variant_compact(
     writer: &mut impl fmt::Write,
     state: &mut State,
-    node: Node,
+    variant: EnumVariant,
     variant_len_max: usize,
 ) -> Result {
-    let ident = node.child(0).expect("valid enum variant");
+    let ident = variant
+        .name()
+        .or_missing(variant.syntax(), "enum variant name")?;
     let ident = state.text(ident);
 
     let spaces = " ".repeat(variant_len_max.saturating_sub(ident.len()));
 
-    let mut field = None;
-    let mut cursor = node.walk();
-    for descendant in node.children(&mut cursor) {
-        if descendant.kind() == "property" {
-            field.replace(descendant);
-            break;
-        }
-    }
-
-    if let Some(field) = field {
-        let field_key = state.text(field.child(0).expect("valid tagged enum field"));
-        let field_typ = state.text(field.child(2).expect("valid tagged enum field"));
+    if let Some(field) = variant.properties().first() {
+        let field_node = field.syntax();
+        let field_key = field
+            .name()
+            .or_missing(field_node, "enum variant field name")?;
+        let field_typ = field
+            .ty()
+            .or_missing(field_node, "enum variant field type")?;
+        let field_key = state.text(field_key);
+        let field_typ = state.text(field_typ.syntax());
 
         write!(writer, "{ident}{spaces} {{ {field_key}: {field_typ} }}")?;
     } else {
@@ -108,7 +117,10 @@ pub(super) fn format_tagged_multiline(
     state: &mut State,
     node: Node,
 ) -> Result {
-    let tag = node.child_by_field_name("tag").unwrap();
+    let tag = EnumType::cast(node)
+        .or_unexpected(node)?
+        .tag()
+        .or_missing(node, "enum tag")?;
     let tag = state.text(tag);
 
     writeln!(writer, "enum {tag} {{")?;
@@ -117,9 +129,9 @@ pub(super) fn format_tagged_multiline(
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        if child.kind() == "enum_variant" {
+        if let Some(variant) = EnumVariant::cast(child) {
             write!(writer, "{}", state.indent())?;
-            format_variant_multiline(writer, state, child)?;
+            format_variant_multiline(writer, state, variant)?;
             writeln!(writer, ",")?;
         } else if is_known_node(child) {
             write!(writer, "{}", state.indent())?;
@@ -140,8 +152,13 @@ pub(super) fn format_tagged_multiline(
     Ok(())
 }
 
-fn format_variant_multiline(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let ident = node.child(0).expect("valid enum variant");
+fn format_variant_multiline(
+    writer: &mut impl fmt::Write,
+    state: &mut State,
+    variant: EnumVariant,
+) -> Result {
+    let node = variant.syntax();
+    let ident = variant.name().or_missing(node, "enum variant name")?;
     let ident = state.text(ident);
 
     if is_type_empty(node, Some(1)) {
@@ -153,12 +170,14 @@ fn format_variant_multiline(writer: &mut impl fmt::Write, state: &mut State, nod
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor).skip(2) {
-            if child.kind() == "property" {
-                let key = child.child(0).expect("valid enum variant field");
-                let typ = child.child(2).expect("valid enum variant field");
+            if let Some(property) = Property::cast(child) {
+                let key = property
+                    .name()
+                    .or_missing(child, "enum variant field name")?;
+                let typ = property.ty().or_missing(child, "enum variant field type")?;
 
                 write!(writer, "{}{}: ", state.indent(), state.text(key))?;
-                format_node(writer, state, typ)?;
+                format_node(writer, state, typ.syntax())?;
                 writeln!(writer, ",")?;
             } else if is_known_node(child) {
                 write!(writer, "{}", state.indent())?;
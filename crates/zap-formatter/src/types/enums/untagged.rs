@@ -1,11 +1,16 @@
 use std::fmt;
 
 use zap_language::{
+    ast::{AstNode, EnumVariant},
     tree_sitter::Node,
     tree_sitter_utils::{is_comment_node, is_known_node},
 };
 
-use crate::{format_node, result::Result, state::State};
+use crate::{
+    format_node,
+    result::{OptionExt, Result},
+    state::State,
+};
 
 /**
     Formats an untagged enum as a single line.
@@ -62,15 +67,11 @@ pub(super) fn format_untagged_grid(
 
     state.increase_depth();
 
-    let chunk = match identifiers.len() {
-        9 => 3,
-        16 => 4,
-        25 => 5,
-        36 => 6,
-        _ => unreachable!(),
-    };
+    // NOTE: Grids are only used for square numbers of identifiers, but we use
+    // non-exact chunks here so that no identifiers can ever get lost on output
+    let chunk = identifiers.len().isqrt().max(1);
 
-    for identifier_chunk in identifiers.chunks_exact(chunk) {
+    for identifier_chunk in identifiers.chunks(chunk) {
         write!(writer, "{}", state.indent())?;
         for (index, identifier) in identifier_chunk.iter().enumerate() {
             if index != 0 {
@@ -116,8 +117,8 @@ pub(super) fn format_untagged_multiline(
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        if child.kind() == "enum_variant" {
-            let ident = child.child(0).expect("valid enum variant");
+        if let Some(variant) = EnumVariant::cast(child) {
+            let ident = variant.name().or_missing(child, "enum variant name")?;
             writeln!(writer, "{}{},", state.indent(), state.text(ident))?;
         } else if is_known_node(child) {
             write!(writer, "{}", state.indent())?;
@@ -1,15 +1,23 @@
 use std::fmt;
 
-use zap_language::tree_sitter::Node;
+use zap_language::{
+    ast::{AstNode, MapType},
+    tree_sitter::Node,
+};
 
-use crate::{format_node, result::Result, state::State};
+use crate::{
+    format_node,
+    result::{OptionExt, Result},
+    state::State,
+};
 
 pub(crate) fn format_map(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let key = node.child_by_field_name("key_type").expect("valid map");
-    let val = node.child_by_field_name("value_type").expect("valid map");
+    let map = MapType::cast(node).or_unexpected(node)?;
+    let key = map.key().or_missing(node, "map key type")?;
+    let val = map.value().or_missing(node, "map value type")?;
 
-    write!(writer, "map {{ [{}]: ", state.text(key))?;
-    format_node(writer, state, val)?;
+    write!(writer, "map {{ [{}]: ", state.text(key.syntax()))?;
+    format_node(writer, state, val.syntax())?;
     write!(writer, " }}")?;
 
     Ok(())
@@ -1,9 +1,16 @@
 use std::fmt;
 
-use zap_language::tree_sitter::Node;
+use zap_language::{
+    ast::{AstNode, NamespacedType, OptionalType},
+    tree_sitter::Node,
+};
 
 use crate::basic::plain::format_plain;
-use crate::{format_node, result::Result, state::State};
+use crate::{
+    format_node,
+    result::{OptionExt, Result},
+    state::State,
+};
 
 mod arrays;
 mod enums;
@@ -56,23 +63,24 @@ pub(crate) fn format_type(writer: &mut impl fmt::Write, state: &mut State, node:
 }
 
 fn format_namespaced(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let mut cursor = node.walk();
-    for namespace in node.children_by_field_name("namespace", &mut cursor) {
+    let namespaced = NamespacedType::cast(node).or_unexpected(node)?;
+    for namespace in namespaced.namespaces() {
         format_node(writer, state, namespace)?;
         write!(writer, ".")?;
     }
 
-    let typ = node
-        .child_by_field_name("type")
-        .expect("valid namespaced type");
+    let typ = namespaced.name().or_missing(node, "namespaced type name")?;
     format_type(writer, state, typ)?;
 
     Ok(())
 }
 
 fn format_optional(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let typ = node.child(0).expect("valid optional type");
-    format_type(writer, state, typ)?;
+    let typ = OptionalType::cast(node)
+        .or_unexpected(node)?
+        .inner()
+        .or_missing(node, "optional inner type")?;
+    format_type(writer, state, typ.syntax())?;
     write!(writer, "?")?;
     Ok(())
 }
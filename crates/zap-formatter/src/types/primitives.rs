@@ -1,24 +1,32 @@
 use std::fmt;
 
-use zap_language::tree_sitter::Node;
+use zap_language::{
+    ast::{AstNode, PrimitiveType},
+    tree_sitter::Node,
+};
 
-use crate::{basic::plain::format_plain, result::Result, state::State};
+use crate::{
+    basic::plain::format_plain,
+    result::{OptionExt, Result},
+    state::State,
+};
 
 pub(crate) fn format_primitive(
     writer: &mut impl fmt::Write,
     state: &mut State,
     node: Node,
 ) -> Result {
-    let prim_node = node.child(0).expect("valid primitive");
+    let prim = PrimitiveType::cast(node).or_unexpected(node)?;
+    let prim_node = prim.name().or_missing(node, "primitive name")?;
     let prim_text = state.text(prim_node);
 
-    if let Some(sep_node) = node.child(1) {
+    if let Some(sep_node) = prim.separator() {
         let sep_text = state.text(sep_node);
         if sep_text == "." {
-            let spec_text = node.child(2).map(|n| state.text(n)).unwrap_or_default();
+            let spec_text = prim.specifier().map(|n| state.text(n)).unwrap_or_default();
             write!(writer, "{prim_text}.{spec_text}")?;
         } else if sep_text == "(" {
-            let spec_text = node.child(2).map(|n| state.text(n)).unwrap_or_default();
+            let spec_text = prim.specifier().map(|n| state.text(n)).unwrap_or_default();
             write!(writer, "{prim_text}({spec_text})")?;
         } else {
             format_plain(writer, state, node)?;
@@ -1,14 +1,22 @@
 use std::fmt;
 
-use zap_language::tree_sitter::Node;
+use zap_language::{
+    ast::{AstNode, SetType},
+    tree_sitter::Node,
+};
 
-use crate::{format_node, result::Result, state::State};
+use crate::{
+    format_node,
+    result::{OptionExt, Result},
+    state::State,
+};
 
 pub(crate) fn format_set(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
-    let typ = node.child_by_field_name("type").expect("valid set");
+    let set = SetType::cast(node).or_unexpected(node)?;
+    let typ = set.ty().or_missing(node, "set type")?;
 
     write!(writer, "set {{ ")?;
-    format_node(writer, state, typ)?;
+    format_node(writer, state, typ.syntax())?;
     write!(writer, " }}")?;
 
     Ok(())
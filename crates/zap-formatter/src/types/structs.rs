@@ -1,11 +1,16 @@
 use std::fmt;
 
 use zap_language::{
+    ast::{AstNode, Property},
     tree_sitter::Node,
     tree_sitter_utils::{is_comment_node, is_known_node, is_type_empty},
 };
 
-use crate::{format_node, result::Result, state::State};
+use crate::{
+    format_node,
+    result::{OptionExt, Result},
+    state::State,
+};
 
 pub(crate) fn format_struct(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
     if is_type_empty(node, None) {
@@ -17,12 +22,12 @@ pub(crate) fn format_struct(writer: &mut impl fmt::Write, state: &mut State, nod
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            if child.kind() == "property" {
-                let key = child.child(0).expect("valid struct field");
-                let typ = child.child(2).expect("valid struct field");
+            if let Some(property) = Property::cast(child) {
+                let key = property.name().or_missing(child, "struct field name")?;
+                let typ = property.ty().or_missing(child, "struct field type")?;
 
                 write!(writer, "{}{}: ", state.indent(), state.text(key))?;
-                format_node(writer, state, typ)?;
+                format_node(writer, state, typ.syntax())?;
                 writeln!(writer, ",")?;
             } else if is_known_node(child) {
                 write!(writer, "{}", state.indent())?;
@@ -1,11 +1,16 @@
 use std::fmt;
 
 use zap_language::{
+    ast::{AstNode, TupleValue},
     tree_sitter::Node,
     tree_sitter_utils::{is_comment_node, is_known_node, is_type_empty},
 };
 
-use crate::{format_node, result::Result, state::State};
+use crate::{
+    format_node,
+    result::{OptionExt, Result},
+    state::State,
+};
 
 pub(crate) fn format_tuple(writer: &mut impl fmt::Write, state: &mut State, node: Node) -> Result {
     if is_type_empty(node, None) {
@@ -17,17 +22,15 @@ pub(crate) fn format_tuple(writer: &mut impl fmt::Write, state: &mut State, node
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            if child.kind() == "tuple_value" {
-                let typ = child
-                    .child_by_field_name("type")
-                    .expect("valid tuple value");
+            if let Some(value) = TupleValue::cast(child) {
+                let typ = value.ty().or_missing(child, "tuple value type")?;
 
-                if let Some(name) = child.child_by_field_name("name") {
+                if let Some(name) = value.name() {
                     write!(writer, "{}{}: ", state.indent(), state.text(name))?;
-                    format_node(writer, state, typ)?;
+                    format_node(writer, state, typ.syntax())?;
                 } else {
                     write!(writer, "{}", state.indent())?;
-                    format_node(writer, state, typ)?;
+                    format_node(writer, state, typ.syntax())?;
                 }
 
                 writeln!(writer, ",")?;
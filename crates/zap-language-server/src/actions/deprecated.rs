@@ -0,0 +1,50 @@
+use async_language_server::{
+    lsp_types::{CodeAction, Diagnostic, TextEdit},
+    server::Document,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_language::{
+    ast::{AstNode, PrimitiveType},
+    tree_sitter_utils::AncestorIterator,
+};
+
+use super::{diagnostic_node, quick_fix};
+
+/**
+    Replaces deprecated syntax with its modern equivalent.
+
+    Currently handles the parenthesized primitive specifier syntax, such
+    as `Instance(Part)` and `string(utf8)`, which is now `Instance.Part`.
+*/
+pub fn fix(doc: &Document, diagnostic: &Diagnostic) -> Vec<CodeAction> {
+    let Some(primitive) = diagnostic_node(doc, diagnostic)
+        .and_then(|node| AncestorIterator::new(node).find_map(PrimitiveType::cast))
+    else {
+        return Vec::new();
+    };
+
+    let is_parenthesized = primitive
+        .separator()
+        .is_some_and(|sep| doc.node_text(sep) == "(");
+    let (Some(name), Some(specifier)) = (primitive.name(), primitive.specifier()) else {
+        return Vec::new();
+    };
+    if !is_parenthesized {
+        return Vec::new();
+    }
+
+    let name = doc.node_text(name);
+    let specifier = doc.node_text(specifier);
+    let edit = TextEdit {
+        range: ts_range_to_lsp_range(primitive.syntax().range()),
+        new_text: format!("{name}.{specifier}"),
+    };
+
+    vec![quick_fix(
+        doc,
+        diagnostic,
+        format!("Replace with `{name}.{specifier}`"),
+        vec![edit],
+        true,
+    )]
+}
@@ -0,0 +1,174 @@
+use async_language_server::{
+    lsp_types::{CodeAction, CodeActionKind, Command, Position, Range, TextEdit},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+};
+use zap_language::tree_sitter_utils::AncestorIterator;
+
+use crate::utils::is_namespace;
+
+use super::{RENAME_COMMAND, indent, leading_comments, namespace_depth, refactor, unique_name};
+
+/**
+    Extracts an inline struct, enum, map or set type into a new named
+    type declaration in the enclosing namespace, replacing the original
+    inline type with a reference to the new declaration.
+
+    The new name is derived from the surrounding field or declaration,
+    and the client is then asked to let the user rename it.
+*/
+pub fn refactor_extract(doc: &Document, range: Range) -> Vec<CodeAction> {
+    let Some(root) = doc.node_at_root() else {
+        return Vec::new();
+    };
+    let Some(node) = root.descendant_for_point_range(
+        lsp_position_to_ts_point(range.start),
+        lsp_position_to_ts_point(range.end),
+    ) else {
+        return Vec::new();
+    };
+
+    let Some(typ) = AncestorIterator::new(node).find(|a| is_extractable(*a)) else {
+        return Vec::new();
+    };
+    if is_already_named(typ) {
+        return Vec::new();
+    }
+
+    // Find the top-level declaration in the nearest namespace,
+    // which is where we will insert the new type declaration
+    let Some(decl) = AncestorIterator::new(typ).find(|a| a.parent().is_some_and(is_namespace))
+    else {
+        return Vec::new();
+    };
+    let Some(namespace) = decl.parent() else {
+        return Vec::new();
+    };
+
+    let Some(name) = unique_name(doc, namespace, &derive_name(doc, typ)) else {
+        return Vec::new();
+    };
+
+    // Format the extracted type, re-indenting it to match the namespace
+    let text = doc.text_bytes();
+    let indent = indent(&text, namespace_depth(namespace));
+    let config = zap_formatter::Config::new(text.as_slice());
+
+    let mut formatted = String::new();
+    if zap_formatter::format_root(&mut formatted, config, typ).is_err() {
+        formatted = doc.node_text(typ);
+    }
+    let formatted = formatted.replace('\n', &format!("\n{indent}"));
+
+    // Insert before any comments directly above the declaration, so
+    // that those comments stay attached to the original declaration
+    #[allow(clippy::cast_possible_truncation)]
+    let insert_line = leading_comments(decl)
+        .first()
+        .map_or(decl, |c| *c)
+        .range()
+        .start_point
+        .row as u32;
+    let insert_pos = Position::new(insert_line, 0);
+
+    let edits = vec![
+        TextEdit {
+            range: Range::new(insert_pos, insert_pos),
+            new_text: format!("{indent}type {name} = {formatted}\n\n"),
+        },
+        TextEdit {
+            range: ts_range_to_lsp_range(typ.range()),
+            new_text: name.clone(),
+        },
+    ];
+
+    #[allow(clippy::cast_possible_truncation)]
+    let name_pos = Position::new(insert_line, (indent.len() + "type ".len()) as u32);
+    let command = Command {
+        title: String::from("Rename extracted type"),
+        command: String::from(RENAME_COMMAND),
+        arguments: Some(vec![
+            serde_json::to_value(doc.url()).unwrap_or_default(),
+            serde_json::to_value(name_pos).unwrap_or_default(),
+        ]),
+    };
+
+    vec![CodeAction {
+        command: Some(command),
+        ..refactor(
+            doc,
+            CodeActionKind::REFACTOR_EXTRACT,
+            format!("Extract to named type `{name}`"),
+            edits,
+        )
+    }]
+}
+
+fn is_extractable(node: Node) -> bool {
+    // NOTE: Tuples are not types on their own, they only exist as function
+    // args and rets, so extracting them would change the function signature
+    matches!(
+        node.kind(),
+        "struct_type" | "enum_type" | "map_type" | "set_type"
+    )
+}
+
+fn is_already_named(node: Node) -> bool {
+    let parent = match node.parent() {
+        Some(p) if p.kind() == "type" => p.parent(),
+        p => p,
+    };
+    parent.is_some_and(|p| p.kind() == "type_declaration")
+}
+
+/**
+    Derives a name for an extracted type from its surroundings:
+
+    - Struct fields and tuple values use their field name: `position` -> `Position`
+    - Enum variants use their variant name
+    - Event data, function args and rets use the event or function name: `PlayerJoinedData`
+*/
+fn derive_name(doc: &Document, typ: Node) -> String {
+    for ancestor in AncestorIterator::new(typ).skip(1) {
+        let named = match ancestor.kind() {
+            "property" | "tuple_value" => ancestor.child_by_field_name("name"),
+            "enum_variant" => ancestor.child(0),
+            _ => None,
+        };
+        if let Some(name) = named {
+            return to_pascal_case(&doc.node_text(name));
+        }
+
+        let suffix = match ancestor.kind() {
+            "event_data_field" => "Data",
+            "function_args_field" => "Args",
+            "function_rets_field" => "Rets",
+            _ => continue,
+        };
+        if let Some(name) = ancestor
+            .parent()
+            .and_then(|decl| decl.child_by_field_name("name"))
+        {
+            return format!("{}{suffix}", to_pascal_case(&doc.node_text(name)));
+        }
+    }
+
+    String::from("NewType")
+}
+
+fn to_pascal_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut upper = true;
+    for c in s.chars() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
@@ -0,0 +1,139 @@
+use async_language_server::{
+    lsp_types::{CodeAction, Diagnostic, Position, Range, TextEdit},
+    server::Document,
+    tree_sitter_utils::{ts_point_to_lsp_position, ts_range_contains_lsp_position},
+};
+use zap_language::{
+    docs::find_variants,
+    tree_sitter_utils::{AncestorIterator, is_field_node},
+};
+
+use crate::utils::find_option_value;
+
+use super::{diagnostic_node, indent, quick_fix};
+
+const EVENT_FIELDS: [&str; 4] = [
+    "event_from_field",
+    "event_type_field",
+    "event_call_field",
+    "event_data_field",
+];
+const FUNCTION_FIELDS: [&str; 1] = ["function_call_field"];
+
+/**
+    Inserts any missing required fields into event and function declarations.
+*/
+pub fn fix(doc: &Document, diagnostic: &Diagnostic) -> Vec<CodeAction> {
+    let Some(decl) = diagnostic_node(doc, diagnostic).and_then(|node| {
+        AncestorIterator::new(node)
+            .find(|a| matches!(a.kind(), "event_declaration" | "function_declaration"))
+    }) else {
+        return Vec::new();
+    };
+    if !ts_range_contains_lsp_position(decl.range(), diagnostic.range.start) {
+        return Vec::new();
+    }
+
+    let required = if decl.kind() == "event_declaration" {
+        EVENT_FIELDS.as_slice()
+    } else {
+        FUNCTION_FIELDS.as_slice()
+    };
+
+    let mut cursor = decl.walk();
+    let fields = decl
+        .children(&mut cursor)
+        .filter(|c| is_field_node(*c))
+        .collect::<Vec<_>>();
+
+    // The call field of events is optional when there is a default for it
+    let has_call_default = find_option_value(doc, "call_default").is_some();
+    let missing = required
+        .iter()
+        .filter(|kind| !(has_call_default && **kind == "event_call_field"))
+        .filter(|kind| !fields.iter().any(|f| f.kind() == **kind))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Vec::new();
+    }
+
+    let mut cursor = decl.walk();
+    let Some(close) = decl
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "}")
+        .last()
+    else {
+        return Vec::new();
+    };
+
+    // Match the indentation that the formatter would use
+    let depth = AncestorIterator::new(decl)
+        .filter(|a| a.kind() == "namespace_declaration")
+        .count();
+    let text = doc.text_bytes();
+    let outer_indent = indent(&text, depth);
+    let inner_indent = indent(&text, depth + 1);
+
+    let mut lines = String::new();
+    for kind in &missing {
+        let name = kind
+            .trim_start_matches("event_")
+            .trim_start_matches("function_")
+            .trim_end_matches("_field");
+        let value = default_field_value(kind);
+        lines.push_str(&format!("{inner_indent}{name}: {value},\n"));
+    }
+
+    let mut edits = Vec::new();
+
+    // The last existing field may be missing its trailing comma
+    if let Some(last) = fields.last() {
+        if last.next_sibling().is_none_or(|s| s.kind() != ",") {
+            edits.push(TextEdit {
+                range: Range::new(
+                    ts_point_to_lsp_position(last.range().end_point),
+                    ts_point_to_lsp_position(last.range().end_point),
+                ),
+                new_text: String::from(","),
+            });
+        }
+    }
+
+    // Insert on new lines right before the closing brace, which may either
+    // be on its own line, or on the same line as other contents: `{}`
+    let close_start = ts_point_to_lsp_position(close.range().start_point);
+    let close_on_own_line = fields
+        .last()
+        .map_or(decl.range().start_point.row, |f| f.range().end_point.row)
+        < close.range().start_point.row;
+    let (position, new_text) = if close_on_own_line {
+        let line_start = Position::new(close_start.line, 0);
+        (line_start, lines)
+    } else {
+        (close_start, format!("\n{lines}{outer_indent}"))
+    };
+
+    edits.push(TextEdit {
+        range: Range::new(position, position),
+        new_text,
+    });
+
+    let title = if missing.len() == 1 {
+        String::from("Add missing field")
+    } else {
+        String::from("Add missing fields")
+    };
+
+    vec![quick_fix(doc, diagnostic, title, edits, true)]
+}
+
+fn default_field_value(kind: &str) -> String {
+    if kind == "event_data_field" {
+        return String::from("struct {}");
+    }
+
+    find_variants([kind])
+        .and_then(|(_, variants)| variants.first())
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
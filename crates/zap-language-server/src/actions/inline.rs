@@ -0,0 +1,126 @@
+use async_language_server::{
+    lsp_types::{CodeAction, CodeActionKind, Position, Range, TextEdit},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_analysis::{DeclaredType, ReferencedType, Source};
+use zap_language::tree_sitter_utils::AncestorIterator;
+
+use crate::utils::{is_namespace, qualified_type_text, replace_bytes, source};
+
+use super::{namespace_depth, refactor, reformat};
+
+/**
+    Inlines a type alias, replacing every reference to it with the body
+    of its declaration, and then removes the declaration itself.
+
+    Type references inside of the inlined body are qualified as necessary
+    to keep resolving to the same declarations from their new namespace,
+    and all declarations containing an inlined reference are reformatted.
+*/
+pub fn refactor_inline(doc: &Document, range: Range) -> Vec<CodeAction> {
+    let Some(src) = source(doc) else {
+        return Vec::new();
+    };
+    let Some(node) = doc.node_at_position_named(range.start) else {
+        return Vec::new();
+    };
+    let Some(declaration) = find_declaration(&src, node) else {
+        return Vec::new();
+    };
+    let Some(value) = declaration.as_ref().child_by_field_name("value") else {
+        return Vec::new();
+    };
+
+    // Recursive types can not be inlined, they would never end
+    let references = declaration.resolve_references(&src);
+    let declaration_bytes = declaration.as_ref().byte_range();
+    if references
+        .iter()
+        .any(|r| declaration_bytes.contains(&r.as_ref().start_byte()))
+    {
+        return Vec::new();
+    }
+
+    // Group references by the outermost declaration they are a part of,
+    // each of those declarations will be replaced and reformatted once
+    let mut affected: Vec<(Node, Vec<ReferencedType>)> = Vec::new();
+    for reference in references {
+        let Some(outer) = AncestorIterator::new(*reference.as_ref())
+            .find(|a| a.parent().is_some_and(is_namespace))
+        else {
+            return Vec::new();
+        };
+        match affected.iter_mut().find(|(o, _)| *o == outer) {
+            Some((_, refs)) => refs.push(reference),
+            None => affected.push((outer, vec![reference])),
+        }
+    }
+
+    let text = src.text().as_bytes();
+    let mut edits = Vec::new();
+    for (outer, refs) in affected {
+        let Some(namespace) = outer.parent() else {
+            return Vec::new();
+        };
+        // If any reference site can not see all of the types used in the body,
+        // inlining would produce broken references, so we don't offer it at all
+        let Some(body) = qualified_type_text(&src, value, namespace) else {
+            return Vec::new();
+        };
+
+        let replacements = refs
+            .iter()
+            .map(|r| (r.as_ref().byte_range(), body.clone()))
+            .collect::<Vec<_>>();
+        let replaced = replace_bytes(text, outer.byte_range(), &replacements);
+
+        edits.push(TextEdit {
+            range: ts_range_to_lsp_range(outer.range()),
+            new_text: reformat(&replaced, namespace_depth(namespace)),
+        });
+    }
+
+    // Remove full lines, including indentation and the trailing newline
+    let decl_range = ts_range_to_lsp_range(declaration.declaration_range());
+    edits.push(TextEdit {
+        range: Range::new(
+            Position::new(decl_range.start.line, 0),
+            Position::new(decl_range.end.line + 1, 0),
+        ),
+        new_text: String::new(),
+    });
+
+    vec![refactor(
+        doc,
+        CodeActionKind::REFACTOR_INLINE,
+        format!("Inline type `{}`", declaration.identifier_text(&src)),
+        edits,
+    )]
+}
+
+/**
+    Finds the type declaration for the identifier node, which
+    may either be the name of the declaration, or a reference.
+*/
+fn find_declaration<'a>(src: &Source<'a>, node: Node<'a>) -> Option<DeclaredType<'a>> {
+    let parent = node.parent()?;
+
+    if let Some(decl) = DeclaredType::from_node(parent) {
+        if decl.identifier_range() == node.range() {
+            return Some(decl);
+        }
+    }
+
+    let reference = if parent.kind() == "namespaced_type" {
+        if parent.child_by_field_name("type") != Some(node) {
+            return None;
+        }
+        parent
+    } else {
+        node
+    };
+
+    ReferencedType::from_node(reference)?.resolve_declaration(src)
+}
@@ -0,0 +1,330 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use async_language_server::{
+    lsp_types::{
+        CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, NumberOrString, Range,
+        TextEdit, WorkspaceEdit,
+    },
+    server::Document,
+    tree_sitter::{Node, Parser},
+};
+use zap_language::tree_sitter_utils::{AncestorIterator, is_comment_node};
+
+use crate::{
+    codegen::STALE_OUTPUT_CODE,
+    diagnostics::{UNUSED_NAMESPACE_CODE, UNUSED_TYPE_CODE},
+};
+
+mod deprecated;
+mod extract;
+mod fields;
+mod inline;
+mod moves;
+mod options;
+mod stale;
+mod unused;
+mod variants;
+
+/**
+    A quick fix for a single diagnostic.
+
+    Quick fixes only receive diagnostics with codes that are routed to them
+    by [`quick_fixes_for`], but should still return an empty list for any
+    diagnostic that turns out to be something they do not know how to fix.
+*/
+type QuickFix = fn(&Document, &Diagnostic) -> Vec<CodeAction>;
+
+/**
+    Codes of syntax errors reported by the Zap compiler, such as
+    unexpected tokens, missing fields, and invalid `call` variants.
+
+    The Zap compiler groups its codes by the stage that reports them, see
+    `Report::code` in `zap/src/parser/reports.rs` of the `libzap` dependency.
+*/
+const PARSER_CODES: RangeInclusive<u32> = 2000..=2999;
+
+/**
+    Codes of semantic errors reported by the Zap compiler, such as duplicate declarations.
+*/
+const ANALYZER_CODES: RangeInclusive<u32> = 3000..=3999;
+
+/**
+    Codes of deprecation warnings reported by the Zap compiler.
+*/
+const DEPRECATION_CODES: RangeInclusive<u32> = 4000..=4999;
+
+/**
+    Returns the quick fixes that can fix diagnostics with the code of the given diagnostic.
+
+    Diagnostics from the language server itself are routed by their exact code,
+    and diagnostics from the Zap compiler by the range that their code is in.
+*/
+fn quick_fixes_for(diagnostic: &Diagnostic) -> &'static [QuickFix] {
+    if let Some(NumberOrString::String(code)) = &diagnostic.code {
+        match code.as_str() {
+            UNUSED_TYPE_CODE | UNUSED_NAMESPACE_CODE => return &[unused::fix],
+            STALE_OUTPUT_CODE => return &[stale::fix],
+            _ => {}
+        }
+    }
+    match diagnostic_code(diagnostic) {
+        Some(code) if PARSER_CODES.contains(&code) => &[fields::fix, variants::fix],
+        Some(code) if ANALYZER_CODES.contains(&code) => &[options::fix],
+        Some(code) if DEPRECATION_CODES.contains(&code) => &[deprecated::fix],
+        _ => &[],
+    }
+}
+
+/**
+    A refactoring for the given selection range.
+
+    Refactorings should return an empty list if the
+    selection is not something that they can refactor.
+*/
+type Refactor = fn(&Document, Range) -> Vec<CodeAction>;
+
+const REFACTORS: &[Refactor] = &[
+    extract::refactor_extract,
+    inline::refactor_inline,
+    moves::refactor_move,
+];
+
+/**
+    Code action kind for moving declarations, not yet a part of [`CodeActionKind`].
+*/
+pub const REFACTOR_MOVE: CodeActionKind = CodeActionKind::new("refactor.move");
+
+/**
+    The client-side command that starts renaming the symbol at a given position.
+
+    Arguments are the document uri and the position of the symbol.
+*/
+pub const RENAME_COMMAND: &str = "zap.rename";
+
+/**
+    Gathers all available quick fixes for the given diagnostics.
+*/
+pub fn quick_fixes(doc: &Document, diagnostics: &[Diagnostic]) -> Vec<CodeActionOrCommand> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.source.as_deref() == Some("Zap"))
+        .flat_map(|diagnostic| {
+            quick_fixes_for(diagnostic)
+                .iter()
+                .flat_map(|fix| fix(doc, diagnostic))
+        })
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+/**
+    Gathers all available refactorings for the given selection range.
+*/
+pub fn refactors(doc: &Document, range: Range) -> Vec<CodeActionOrCommand> {
+    REFACTORS
+        .iter()
+        .flat_map(|refactor| refactor(doc, range))
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+/**
+    Creates a new quick fix code action that applies the given edits to the document.
+*/
+fn quick_fix(
+    doc: &Document,
+    diagnostic: &Diagnostic,
+    title: impl Into<String>,
+    edits: Vec<TextEdit>,
+    is_preferred: bool,
+) -> CodeAction {
+    CodeAction {
+        title: title.into(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(doc.url().clone(), edits)])),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        is_preferred: Some(is_preferred),
+        ..Default::default()
+    }
+}
+
+/**
+    Creates a new refactoring code action that applies the given edits to the document.
+*/
+fn refactor(
+    doc: &Document,
+    kind: CodeActionKind,
+    title: impl Into<String>,
+    edits: Vec<TextEdit>,
+) -> CodeAction {
+    CodeAction {
+        title: title.into(),
+        kind: Some(kind),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(doc.url().clone(), edits)])),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        ..Default::default()
+    }
+}
+
+/**
+    Returns the given name if no type or namespace in the given namespace
+    is already using it, otherwise appends the first free number to it.
+
+    Returns `None` if no free name could be found.
+*/
+fn unique_name(doc: &Document, namespace: Node, name: &str) -> Option<String> {
+    let mut cursor = namespace.walk();
+    let taken = namespace
+        .children(&mut cursor)
+        .filter(|c| matches!(c.kind(), "type_declaration" | "namespace_declaration"))
+        .filter_map(|c| c.child_by_field_name("name"))
+        .map(|n| doc.node_text(n))
+        .collect::<Vec<_>>();
+
+    if !taken.iter().any(|t| t == name) {
+        return Some(name.to_string());
+    }
+
+    // One more candidate than there are taken names, so at least one is free
+    (2..=taken.len() + 2)
+        .map(|n| format!("{name}{n}"))
+        .find(|candidate| !taken.contains(candidate))
+}
+
+/**
+    Reformats the source of a single declaration, indented to the given namespace depth.
+
+    Falls back to the unformatted source if it can not be formatted.
+*/
+fn reformat(source: &str, depth: usize) -> String {
+    let mut parser = Parser::new();
+    if parser
+        .set_language(&zap_language::TS_LANGUAGE.into())
+        .is_err()
+    {
+        return source.to_string();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return source.to_string();
+    };
+    let Some(decl) = tree.root_node().named_child(0) else {
+        return source.to_string();
+    };
+    if tree.root_node().has_error() {
+        return source.to_string();
+    }
+
+    let mut formatted = String::new();
+    let config = zap_formatter::Config::new(source.as_bytes());
+    if zap_formatter::format_root(&mut formatted, config, decl).is_err() {
+        return source.to_string();
+    }
+
+    formatted.replace('\n', &format!("\n{}", indent(source.as_bytes(), depth)))
+}
+
+/**
+    Returns the indentation for the given namespace depth, matching
+    the indentation the formatter uses for the given document source.
+*/
+fn indent(text: &[u8], depth: usize) -> String {
+    zap_formatter::Config::new(text)
+        .indentation()
+        .as_str()
+        .repeat(depth)
+}
+
+/**
+    Returns the nesting depth of the given namespace, where the root is `0`.
+*/
+fn namespace_depth(namespace: Node) -> usize {
+    AncestorIterator::new(namespace)
+        .filter(|a| a.kind() == "namespace_declaration")
+        .count()
+}
+
+/**
+    Returns all comments directly above the given declaration, in source order.
+*/
+fn leading_comments(decl: Node) -> Vec<Node> {
+    let mut comments = Vec::new();
+    let mut start_row = decl.range().start_point.row;
+    let mut current = decl.prev_sibling();
+    while let Some(prev) = current {
+        if !is_comment_node(prev) || prev.range().end_point.row + 1 < start_row {
+            break;
+        }
+        start_row = prev.range().start_point.row;
+        comments.push(prev);
+        current = prev.prev_sibling();
+    }
+    comments.reverse();
+    comments
+}
+
+/**
+    Finds the innermost named node at the start of the given diagnostic.
+*/
+fn diagnostic_node<'a>(doc: &'a Document, diagnostic: &Diagnostic) -> Option<Node<'a>> {
+    doc.node_at_position_named(diagnostic.range.start)
+}
+
+/**
+    Returns the numeric code of the given diagnostic, if it has one.
+*/
+fn diagnostic_code(diagnostic: &Diagnostic) -> Option<u32> {
+    match diagnostic.code.as_ref()? {
+        NumberOrString::Number(n) => u32::try_from(*n).ok(),
+        NumberOrString::String(s) => s.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::fn_addr_eq;
+
+    use async_language_server::lsp_types::{Diagnostic, NumberOrString};
+
+    use super::{QuickFix, deprecated, fields, options, quick_fixes_for, variants};
+
+    fn routes_to(code: &str, expected: &[QuickFix]) -> bool {
+        let diagnostic = Diagnostic {
+            code: Some(NumberOrString::String(code.to_string())),
+            ..Default::default()
+        };
+        let fixes = quick_fixes_for(&diagnostic);
+        fixes.len() == expected.len() && fixes.iter().zip(expected).all(|(a, b)| fn_addr_eq(*a, *b))
+    }
+
+    #[test]
+    fn parser_codes_route_to_field_and_variant_fixes() {
+        assert!(routes_to("2000", &[fields::fix, variants::fix]));
+        assert!(routes_to("2999", &[fields::fix, variants::fix]));
+    }
+
+    #[test]
+    fn analyzer_codes_route_to_option_fixes() {
+        assert!(routes_to("3000", &[options::fix]));
+        assert!(routes_to("3999", &[options::fix]));
+    }
+
+    #[test]
+    fn deprecation_codes_route_to_deprecation_fixes() {
+        assert!(routes_to("4000", &[deprecated::fix]));
+        assert!(routes_to("4999", &[deprecated::fix]));
+    }
+
+    #[test]
+    fn other_codes_route_to_nothing() {
+        assert!(routes_to("1999", &[]));
+        assert!(routes_to("5000", &[]));
+        assert!(routes_to("unknown", &[]));
+    }
+}
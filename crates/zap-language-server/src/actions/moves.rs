@@ -0,0 +1,281 @@
+use async_language_server::{
+    lsp_types::{CodeAction, CodeActionDisabled, Position, Range, TextEdit},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{find_ancestor, ts_point_to_lsp_position, ts_range_to_lsp_range},
+};
+use zap_analysis::{DeclaredNamespace, DeclaredType, ReferencedType, Source};
+use zap_language::tree_sitter_utils::AncestorIterator;
+
+use crate::utils::{is_namespace, namespace_path, replace_bytes, source};
+
+use super::{REFACTOR_MOVE, indent, leading_comments, namespace_depth, refactor, reformat};
+
+/**
+    Moves a type or namespace declaration into another namespace, or to the root,
+    rewriting all references to it so that they resolve to the new location.
+
+    One action is offered per possible destination. Destinations that would
+    cause conflicts, such as a declaration with the same name already existing,
+    or references that would no longer be able to reach the moved declaration,
+    are still offered but disabled, with the conflict as the reason.
+*/
+pub fn refactor_move(doc: &Document, range: Range) -> Vec<CodeAction> {
+    let Some(src) = source(doc) else {
+        return Vec::new();
+    };
+    let root = src.root();
+    let Some(node) = doc.node_at_position_named(range.start) else {
+        return Vec::new();
+    };
+    let Some(decl) = node.parent().filter(|p| {
+        matches!(p.kind(), "type_declaration" | "namespace_declaration")
+            && p.child_by_field_name("name") == Some(node)
+    }) else {
+        return Vec::new();
+    };
+    let Some(source) = decl.parent() else {
+        return Vec::new();
+    };
+
+    let name = src.node_text(node);
+
+    let mut destinations = vec![root];
+    destinations.extend(
+        DeclaredNamespace::find_all(&src)
+            .iter()
+            .map(|ns| *ns.as_ref()),
+    );
+
+    destinations
+        .into_iter()
+        // Moving a namespace into itself, or any of its children, is not possible
+        .filter(|dest| *dest != source && !AncestorIterator::new(*dest).any(|a| a == decl))
+        .map(|dest| {
+            let title = if dest == root {
+                format!("Move `{name}` to root")
+            } else {
+                format!(
+                    "Move `{name}` to `{}`",
+                    describe_namespace(&src, root, dest)
+                )
+            };
+            match move_edits(&src, root, decl, &name, dest) {
+                Ok(edits) => refactor(doc, REFACTOR_MOVE, title, edits),
+                Err(reason) => CodeAction {
+                    title,
+                    kind: Some(REFACTOR_MOVE),
+                    disabled: Some(CodeActionDisabled { reason }),
+                    ..Default::default()
+                },
+            }
+        })
+        .collect()
+}
+
+/**
+    Creates all the edits necessary to move the declaration to the given destination,
+    or returns a human-readable reason for why the move would cause a conflict.
+*/
+fn move_edits(
+    src: &Source,
+    root: Node,
+    decl: Node,
+    name: &str,
+    dest: Node,
+) -> Result<Vec<TextEdit>, String> {
+    let is_type = decl.kind() == "type_declaration";
+    let noun = if is_type { "type" } else { "namespace" };
+
+    // 1. Check for an existing declaration with the same name at the destination
+    let mut cursor = dest.walk();
+    let existing = dest.children(&mut cursor).any(|child| {
+        child.kind() == decl.kind()
+            && child
+                .child_by_field_name("name")
+                .is_some_and(|n| src.node_text(n) == name)
+    });
+    if existing {
+        return Err(format!(
+            "A {noun} named `{name}` already exists in {}",
+            describe_destination(src, root, dest)
+        ));
+    }
+
+    let unreachable = |site: Node| {
+        format!(
+            "`{name}` would not be reachable from references in {}",
+            describe_destination(src, root, site)
+        )
+    };
+
+    // 2. Rewrite all references to the declaration, replacing their namespace
+    //    paths with the path from their own namespace to the destination
+    let mut edits = Vec::new();
+    if is_type {
+        let Some(declared) = DeclaredType::from_node(decl) else {
+            return Err(String::from("Invalid type declaration"));
+        };
+        for reference in declared.resolve_references(src) {
+            // Recursive references are requalified along with the declaration body
+            if decl.byte_range().contains(&reference.as_ref().start_byte()) {
+                continue;
+            }
+            let Some(site) = find_ancestor(*reference.as_ref(), is_namespace) else {
+                continue;
+            };
+            let mut path = namespace_path(src, site, dest).ok_or_else(|| unreachable(site))?;
+            path.push(name.to_string());
+            edits.push(TextEdit {
+                range: ts_range_to_lsp_range(reference.reference_range()),
+                new_text: path.join("."),
+            });
+        }
+    } else {
+        let Some(declared) = DeclaredNamespace::from_node(decl) else {
+            return Err(String::from("Invalid namespace declaration"));
+        };
+        for reference in declared.resolve_references(src) {
+            let Some(site) = find_ancestor(*reference.as_ref(), is_namespace) else {
+                continue;
+            };
+            let mut path = namespace_path(src, site, dest).ok_or_else(|| unreachable(site))?;
+            path.push(name.to_string());
+            // Only the path up until and including the namespace is rewritten,
+            // anything after it is still relative to the moved namespace
+            edits.push(TextEdit {
+                range: Range::new(
+                    ts_point_to_lsp_position(reference.reference_range().start_point),
+                    ts_point_to_lsp_position(reference.identifier_range().end_point),
+                ),
+                new_text: path.join("."),
+            });
+        }
+    }
+
+    // 3. Requalify references inside of a moved type, since those are relative to
+    //    its namespace - references inside of a moved namespace stay the same
+    let text = src.text().as_bytes();
+    let mut replacements = Vec::new();
+    if let Some(value) = decl.child_by_field_name("value").filter(|_| is_type) {
+        for inner in ReferencedType::find_all_in(value) {
+            let Some(target) = inner.resolve_declaration(src) else {
+                continue;
+            };
+            let path = if *target.as_ref() == decl {
+                vec![name.to_string()]
+            } else {
+                let target_ident = target.identifier_text(src);
+                let mut path = find_ancestor(*target.as_ref(), is_namespace)
+                    .and_then(|target_ns| namespace_path(src, dest, target_ns))
+                    .ok_or_else(|| {
+                        format!(
+                            "`{target_ident}` would not be reachable from {}",
+                            describe_destination(src, root, dest)
+                        )
+                    })?;
+                path.push(target_ident);
+                path
+            };
+            replacements.push((inner.as_ref().byte_range(), path.join(".")));
+        }
+    }
+
+    // 4. Remove the declaration along with any comments above it
+    let depth = namespace_depth(dest);
+    let indent = indent(text, depth);
+    let comments = leading_comments(decl);
+
+    let first = comments.first().copied().unwrap_or(decl);
+    let decl_range = ts_range_to_lsp_range(decl.range());
+    edits.push(TextEdit {
+        range: Range::new(
+            Position::new(ts_point_to_lsp_position(first.range().start_point).line, 0),
+            Position::new(decl_range.end.line + 1, 0),
+        ),
+        new_text: String::new(),
+    });
+
+    // 5. Insert it at the end of the destination, reformatted and reindented
+    let mut moved = String::new();
+    for comment in comments {
+        moved.push_str(&indent);
+        moved.push_str(src.node_text(comment).trim_end());
+        moved.push('\n');
+    }
+    moved.push_str(&indent);
+    moved.push_str(&reformat(
+        &replace_bytes(text, decl.byte_range(), &replacements),
+        depth,
+    ));
+
+    edits.push(insertion_at_end(text, dest, depth, moved));
+
+    Ok(edits)
+}
+
+/**
+    Creates an edit that inserts the given, already indented,
+    text at the end of the namespace, separated by a blank line.
+*/
+fn insertion_at_end(text: &[u8], namespace: Node, depth: usize, inserted: String) -> TextEdit {
+    let close = if namespace.kind() == "namespace_declaration" {
+        let mut cursor = namespace.walk();
+        namespace
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "}")
+            .last()
+    } else {
+        None
+    };
+
+    let Some(close) = close else {
+        // The root, or a namespace without a closing brace, insert at the very end
+        let end = ts_point_to_lsp_position(namespace.range().end_point);
+        let separator = if end.character == 0 { "\n" } else { "\n\n" };
+        return TextEdit {
+            range: Range::new(end, end),
+            new_text: format!("{separator}{inserted}\n"),
+        };
+    };
+
+    let pos = ts_point_to_lsp_position(close.range().start_point);
+    let line_start = close.start_byte() - close.range().start_point.column;
+    let brace_on_own_line = text[line_start..close.start_byte()]
+        .iter()
+        .all(u8::is_ascii_whitespace);
+
+    if brace_on_own_line {
+        let line_start = Position::new(pos.line, 0);
+        TextEdit {
+            range: Range::new(line_start, line_start),
+            new_text: format!("\n{inserted}\n"),
+        }
+    } else {
+        let outer_indent = indent(text, depth.saturating_sub(1));
+        TextEdit {
+            range: Range::new(pos, pos),
+            new_text: format!("\n{inserted}\n{outer_indent}"),
+        }
+    }
+}
+
+/**
+    Returns the qualified name of a namespace, such as `A.B`.
+*/
+fn describe_namespace(src: &Source, root: Node, namespace: Node) -> String {
+    namespace_path(src, root, namespace)
+        .unwrap_or_default()
+        .join(".")
+}
+
+/**
+    Describes a namespace for use in conflict messages, such as `namespace A.B` or `the root`.
+*/
+fn describe_destination(src: &Source, root: Node, namespace: Node) -> String {
+    if namespace == root {
+        String::from("the root")
+    } else {
+        format!("namespace `{}`", describe_namespace(src, root, namespace))
+    }
+}
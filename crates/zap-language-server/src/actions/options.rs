@@ -0,0 +1,57 @@
+use async_language_server::{
+    lsp_types::{CodeAction, Diagnostic, Position, Range, TextEdit},
+    server::Document,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_language::{
+    ast::{AstNode, OptionDecl},
+    tree_sitter_utils::AncestorIterator,
+};
+
+use super::{diagnostic_node, quick_fix};
+
+/**
+    Removes options that have already been declared earlier in the document.
+*/
+pub fn fix(doc: &Document, diagnostic: &Diagnostic) -> Vec<CodeAction> {
+    let Some(option) = diagnostic_node(doc, diagnostic)
+        .and_then(|node| AncestorIterator::new(node).find_map(OptionDecl::cast))
+    else {
+        return Vec::new();
+    };
+    let Some(name) = option.name().map(|n| doc.node_text(n)) else {
+        return Vec::new();
+    };
+    let option = option.syntax();
+    let Some(parent) = option.parent() else {
+        return Vec::new();
+    };
+
+    let mut cursor = parent.walk();
+    let is_duplicate = parent
+        .children(&mut cursor)
+        .take_while(|c| *c != option)
+        .filter_map(OptionDecl::cast)
+        .any(|c| c.name().is_some_and(|n| doc.node_text(n) == name));
+    if !is_duplicate {
+        return Vec::new();
+    }
+
+    // Remove full lines, including the trailing newline
+    let range = ts_range_to_lsp_range(option.range());
+    let edit = TextEdit {
+        range: Range::new(
+            Position::new(range.start.line, 0),
+            Position::new(range.end.line + 1, 0),
+        ),
+        new_text: String::new(),
+    };
+
+    vec![quick_fix(
+        doc,
+        diagnostic,
+        format!("Remove duplicate option `{name}`"),
+        vec![edit],
+        true,
+    )]
+}
@@ -0,0 +1,26 @@
+use async_language_server::{
+    lsp_types::{CodeAction, CodeActionKind, Command, Diagnostic},
+    server::Document,
+};
+
+use crate::codegen::GENERATE_COMMAND;
+
+/**
+    Regenerates output files that are outdated or missing.
+*/
+pub fn fix(doc: &Document, diagnostic: &Diagnostic) -> Vec<CodeAction> {
+    // Generation writes files, which can't be done using plain text
+    // edits, so this quick fix runs the generate command instead
+    vec![CodeAction {
+        title: String::from("Regenerate output files"),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        command: Some(Command {
+            title: String::from("Regenerate output files"),
+            command: String::from(GENERATE_COMMAND),
+            arguments: Some(vec![serde_json::to_value(doc.url()).unwrap_or_default()]),
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }]
+}
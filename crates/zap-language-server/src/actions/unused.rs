@@ -0,0 +1,38 @@
+use async_language_server::{
+    lsp_types::{CodeAction, Diagnostic, NumberOrString, Range, TextEdit},
+    server::Document,
+};
+
+use crate::diagnostics::{UNUSED_NAMESPACE_CODE, UNUSED_TYPE_CODE};
+
+use super::quick_fix;
+
+/**
+    Removes unused type and namespace declarations.
+*/
+pub fn fix(doc: &Document, diagnostic: &Diagnostic) -> Vec<CodeAction> {
+    let title = match &diagnostic.code {
+        Some(NumberOrString::String(code)) if code == UNUSED_TYPE_CODE => "Remove unused type",
+        Some(NumberOrString::String(code)) if code == UNUSED_NAMESPACE_CODE => {
+            "Remove unused namespace"
+        }
+        _ => return Vec::new(),
+    };
+
+    // The full range of the declaration to remove is stored
+    // in the diagnostic data, when the diagnostic is created
+    let Some(range) = diagnostic
+        .data
+        .clone()
+        .and_then(|d| serde_json::from_value::<Range>(d).ok())
+    else {
+        return Vec::new();
+    };
+
+    let edit = TextEdit {
+        range,
+        new_text: String::new(),
+    };
+
+    vec![quick_fix(doc, diagnostic, title, vec![edit], true)]
+}
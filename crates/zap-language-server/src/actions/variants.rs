@@ -0,0 +1,88 @@
+use async_language_server::{
+    lsp_types::{CodeAction, Diagnostic, TextEdit},
+    server::Document,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_language::{
+    ast::{AstNode, FieldDecl},
+    docs::find_variants,
+    tree_sitter_utils::AncestorIterator,
+};
+
+use super::{diagnostic_node, quick_fix};
+
+/**
+    Replaces invalid `call` variants in event and function
+    declarations with one of the valid variants.
+
+    The closest matching variant, if any, is marked as preferred.
+*/
+pub fn fix(doc: &Document, diagnostic: &Diagnostic) -> Vec<CodeAction> {
+    let Some(field) = diagnostic_node(doc, diagnostic).and_then(|node| {
+        AncestorIterator::new(node)
+            .filter_map(FieldDecl::cast)
+            .find(|f| f.key() == "call")
+    }) else {
+        return Vec::new();
+    };
+
+    let Some((false, variants)) = find_variants([field.syntax().kind()]) else {
+        return Vec::new();
+    };
+    let Some(value) = field.value() else {
+        return Vec::new();
+    };
+
+    let current = doc.node_text(value);
+    if variants.contains(&current.as_str()) {
+        return Vec::new();
+    }
+
+    let closest = variants
+        .iter()
+        .min_by_key(|variant| {
+            edit_distance(&current.to_ascii_lowercase(), &variant.to_ascii_lowercase())
+        })
+        .copied();
+
+    variants
+        .iter()
+        .map(|variant| {
+            let edit = TextEdit {
+                range: ts_range_to_lsp_range(value.range()),
+                new_text: (*variant).to_string(),
+            };
+            quick_fix(
+                doc,
+                diagnostic,
+                format!("Change to `{variant}`"),
+                vec![edit],
+                closest == Some(*variant),
+            )
+        })
+        .collect()
+}
+
+/**
+    Computes the Levenshtein distance between two strings.
+*/
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(row[j + 1])
+            };
+            prev = current;
+        }
+    }
+
+    row[b.len()]
+}
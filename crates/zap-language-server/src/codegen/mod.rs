@@ -0,0 +1,19 @@
+use async_language_server::lsp_types::Url;
+use serde_json::Value;
+
+mod output;
+mod stale;
+mod write;
+
+pub use self::output::{GENERATED_OUTPUT_COMMAND, generated_output};
+pub use self::stale::{STALE_OUTPUT_CODE, StaleOutputs};
+pub use self::write::{GENERATE_COMMAND, GenerationFailures, generate_files};
+
+/**
+    Parses the document url from the first argument of an executed command.
+*/
+pub fn document_url_argument(arguments: &[Value]) -> Option<Url> {
+    arguments
+        .first()
+        .and_then(|argument| serde_json::from_value(argument.clone()).ok())
+}
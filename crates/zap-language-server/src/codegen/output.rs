@@ -0,0 +1,45 @@
+use async_language_server::server::Document;
+use serde_json::{Value, json};
+
+use crate::{compiler::Compiler, diagnostics::zap_diagnostic_to_lsp_diagnostic};
+
+/**
+    Command that returns the generated output for a document, without writing any files.
+
+    Takes the document url as its only argument, and is used by
+    clients to preview the generated code as virtual documents.
+*/
+pub const GENERATED_OUTPUT_COMMAND: &str = "zap.generatedOutput";
+
+/**
+    Runs the code generator on the in-memory contents of the given document.
+
+    Returns an object with a list of `outputs`, containing the `kind`, `path`,
+    `code` and `definitions` of each generated file, and a list of LSP
+    `diagnostics`, which is only non-empty if the generation failed.
+*/
+pub async fn generated_output(doc: &Document, compiler: &Compiler) -> Value {
+    match compiler.generate(doc.url(), doc.text_contents()).await {
+        Ok(files) => {
+            let outputs = files
+                .into_iter()
+                .map(|file| {
+                    json!({
+                        "kind": file.kind.name(),
+                        "path": file.path,
+                        "code": file.code,
+                        "definitions": file.definitions,
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({ "outputs": outputs, "diagnostics": [] })
+        }
+        Err(diagnostics) => {
+            let diagnostics = diagnostics
+                .into_iter()
+                .filter_map(|diag| zap_diagnostic_to_lsp_diagnostic(doc, diag))
+                .collect::<Vec<_>>();
+            json!({ "outputs": [], "diagnostics": diagnostics })
+        }
+    }
+}
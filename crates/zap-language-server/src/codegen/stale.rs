@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use async_language_server::{
+    lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Url},
+    server::Document,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_language::{ast::AstNode, codegen::OutputKind};
+
+use crate::{compiler::Compiler, utils::find_option_declaration};
+
+pub const STALE_OUTPUT_CODE: &str = "stale-output";
+
+/**
+    Warnings for missing or outdated output files of each document.
+
+    Checking requires a full run of the code generator, and reading the output
+    files from disk, so this is only done when a document is opened or saved,
+    instead of every time diagnostics are requested.
+
+    Cheap to clone, all clones share the same underlying warnings.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct StaleOutputs {
+    warnings: Arc<Mutex<HashMap<Url, Vec<Diagnostic>>>>,
+}
+
+impl StaleOutputs {
+    /**
+        Returns the warnings from the most recent check of the given document.
+    */
+    pub fn get(&self, url: &Url) -> Vec<Diagnostic> {
+        self.warnings
+            .lock()
+            .ok()
+            .and_then(|w| w.get(url).cloned())
+            .unwrap_or_default()
+    }
+
+    /**
+        Checks the output files of the given document again, replacing any previous warnings.
+    */
+    pub async fn refresh(&self, doc: &Document, compiler: &Compiler) {
+        let diagnostics = stale_output_diagnostics(doc, compiler).await;
+        if let Ok(mut warnings) = self.warnings.lock() {
+            warnings.insert(doc.url().clone(), diagnostics);
+        }
+    }
+
+    /**
+        Removes all warnings for the given document, such as when it is
+        closed, or right after its output files have been generated.
+
+        Outputs that failed to generate or write are already
+        reported by the generation, and are not warned about again.
+    */
+    pub fn remove(&self, url: &Url) {
+        if let Ok(mut warnings) = self.warnings.lock() {
+            warnings.remove(url);
+        }
+    }
+}
+
+/**
+    Compares the server and client output files on disk against what would be
+    generated from the current contents of the document, and creates a warning
+    on the option declaration for each output file that is missing or outdated.
+*/
+async fn stale_output_diagnostics(doc: &Document, compiler: &Compiler) -> Vec<Diagnostic> {
+    // Documents with errors can not be generated, those errors are more important anyway
+    let Ok(files) = compiler.generate(doc.url(), doc.text_contents()).await else {
+        return Vec::new();
+    };
+    let Some(dir) = doc
+        .url()
+        .to_file_path()
+        .ok()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+    else {
+        return Vec::new();
+    };
+
+    // Only outputs with an explicit option have a line to put the warning on
+    let checked = files
+        .into_iter()
+        .filter(|file| matches!(file.kind, OutputKind::Server | OutputKind::Client))
+        .filter_map(|file| {
+            let decl = find_option_declaration(doc, file.kind.option_name())?;
+            Some((file, ts_range_to_lsp_range(decl.syntax().range())))
+        })
+        .collect::<Vec<_>>();
+
+    let mut diagnostics = Vec::new();
+    for (file, range) in checked {
+        let message = match tokio::fs::read_to_string(dir.join(&file.path)).await {
+            Ok(existing) if normalize(&existing) == normalize(&file.code) => continue,
+            Ok(_) => format!(
+                "Generated {} output is outdated and does not match this file",
+                file.kind.name()
+            ),
+            Err(_) => format!(
+                "Generated {} output has not been written yet",
+                file.kind.name()
+            ),
+        };
+        diagnostics.push(Diagnostic {
+            range,
+            severity: Some(DiagnosticSeverity::WARNING),
+            message,
+            source: Some(String::from("Zap")),
+            code: Some(NumberOrString::String(STALE_OUTPUT_CODE.to_string())),
+            ..Default::default()
+        });
+    }
+
+    diagnostics
+}
+
+fn normalize(code: &str) -> String {
+    code.replace("\r\n", "\n").trim_end().to_string()
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use async_language_server::{
+    lsp_types::{Diagnostic, DiagnosticSeverity, Range, Url},
+    server::Document,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use serde_json::{Value, json};
+use zap_language::{ast::AstNode, codegen::OutputFile};
+
+use crate::{
+    compiler::Compiler, diagnostics::zap_diagnostic_to_lsp_diagnostic,
+    utils::find_option_declaration,
+};
+
+/**
+    Command that generates and writes all output files for a document.
+
+    Takes the document url as its only argument, and is used by clients for manual
+    generation. Generating when saving is handled by the server itself instead.
+*/
+pub const GENERATE_COMMAND: &str = "zap.generate";
+
+/**
+    Failures from the most recent generation of each document, which are not
+    already covered by the regular diagnostics, such as failing to write files.
+
+    Cheap to clone, all clones share the same underlying failures.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct GenerationFailures {
+    failures: Arc<Mutex<HashMap<Url, Vec<Diagnostic>>>>,
+}
+
+impl GenerationFailures {
+    /**
+        Returns diagnostics for all failures from the most recent generation of the given document.
+    */
+    pub fn get(&self, url: &Url) -> Vec<Diagnostic> {
+        self.failures
+            .lock()
+            .ok()
+            .and_then(|f| f.get(url).cloned())
+            .unwrap_or_default()
+    }
+
+    fn set(&self, url: Url, diagnostics: Vec<Diagnostic>) {
+        if let Ok(mut failures) = self.failures.lock() {
+            if diagnostics.is_empty() {
+                failures.remove(&url);
+            } else {
+                failures.insert(url, diagnostics);
+            }
+        }
+    }
+}
+
+/**
+    Runs the code generator on the in-memory contents of the given document,
+    and writes all of the outputs to their paths, relative to the document.
+
+    Returns an object with a list of `written` file paths, and a list of LSP
+    `diagnostics` for anything that prevented generation or writing files.
+*/
+pub async fn generate_files(
+    doc: &Document,
+    compiler: &Compiler,
+    failures: &GenerationFailures,
+) -> Value {
+    let files = match compiler.generate(doc.url(), doc.text_contents()).await {
+        Ok(files) => files,
+        Err(diagnostics) => {
+            // These are the same as the regular diagnostics, so they are not stored
+            let diagnostics = diagnostics
+                .into_iter()
+                .filter_map(|diag| zap_diagnostic_to_lsp_diagnostic(doc, diag))
+                .collect::<Vec<_>>();
+            return json!({ "written": [], "diagnostics": diagnostics });
+        }
+    };
+
+    let Some(dir) = doc
+        .url()
+        .to_file_path()
+        .ok()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+    else {
+        return json!({ "written": [], "diagnostics": [] });
+    };
+
+    let mut written = Vec::new();
+    let mut diagnostics = Vec::new();
+    for file in files {
+        match write_file(&dir, &file).await {
+            Ok(paths) => written.extend(paths),
+            Err(e) => {
+                // Write failures are reported on the option for the output, if there is one
+                let range = find_option_declaration(doc, file.kind.option_name())
+                    .map(|decl| ts_range_to_lsp_range(decl.syntax().range()))
+                    .unwrap_or_default();
+                diagnostics.push(write_failure(&file, range, &e));
+            }
+        }
+    }
+
+    failures.set(doc.url().clone(), diagnostics.clone());
+
+    json!({ "written": written, "diagnostics": diagnostics })
+}
+
+async fn write_file(dir: &Path, file: &OutputFile) -> std::io::Result<Vec<String>> {
+    let path = dir.join(&file.path);
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut written = Vec::new();
+
+    tokio::fs::write(&path, &file.code).await?;
+    written.push(path.display().to_string());
+
+    if let Some(definitions) = &file.definitions {
+        let path = path.with_extension("d.ts");
+        tokio::fs::write(&path, definitions).await?;
+        written.push(path.display().to_string());
+    }
+
+    Ok(written)
+}
+
+fn write_failure(file: &OutputFile, range: Range, error: &std::io::Error) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        message: format!(
+            "Failed to write generated {} output to '{}': {error}",
+            file.kind.name(),
+            file.path.display()
+        ),
+        source: Some(String::from("Zap")),
+        ..Default::default()
+    }
+}
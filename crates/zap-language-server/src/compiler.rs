@@ -0,0 +1,151 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use async_language_server::lsp_types::Url;
+use zap_language::{
+    codegen::{OutputFile, TypePreview},
+    diagnostics::{Diagnostic, internal_compiler_error},
+};
+
+/**
+    Maximum amount of time that the Zap compiler may spend on a single run.
+*/
+const COMPILER_TIME_BUDGET: Duration = Duration::from_secs(5);
+
+/**
+    Runs the Zap compiler on blocking threads, so that
+    it never blocks the runtime that serves requests.
+
+    Runs exceeding the time budget are reported as an internal compiler error.
+    Those runs can not be cancelled, so while one is still running for a
+    document, any new runs for that same document fail right away instead of
+    piling up more stuck threads.
+
+    Cheap to clone, all clones share the same underlying state.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Compiler {
+    stuck: Arc<Mutex<HashSet<Url>>>,
+}
+
+impl Compiler {
+    /**
+        Runs the Zap compiler on the given contents, returning all of its diagnostics.
+    */
+    pub async fn diagnostics(&self, url: &Url, contents: String) -> Vec<Diagnostic> {
+        let owned = contents.clone();
+        self.run(url, &contents, move || {
+            zap_language::diagnostics::parse(&owned)
+        })
+        .await
+        .unwrap_or_else(|diagnostic| vec![diagnostic])
+    }
+
+    /**
+        Runs the Zap code generator on the given contents.
+
+        # Errors
+
+        - If the contents contain any errors, or if the code generator
+          did not finish, in which case all diagnostics are returned
+    */
+    pub async fn generate(
+        &self,
+        url: &Url,
+        contents: String,
+    ) -> Result<Vec<OutputFile>, Vec<Diagnostic>> {
+        let owned = contents.clone();
+        self.run(url, &contents, move || {
+            zap_language::codegen::generate(&owned)
+        })
+        .await
+        .unwrap_or_else(|diagnostic| Err(vec![diagnostic]))
+    }
+
+    /**
+        Previews the generated Luau and TypeScript types for the given Zap types.
+
+        Returns `None` if the types could not be generated, or if the code generator did not finish.
+    */
+    pub async fn preview_types(
+        &self,
+        url: &Url,
+        contents: String,
+        types: Vec<String>,
+    ) -> Option<Vec<TypePreview>> {
+        let owned = contents.clone();
+        self.run(url, &contents, move || {
+            zap_language::codegen::preview_types(&owned, &types)
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+
+    async fn run<T: Send + 'static>(
+        &self,
+        url: &Url,
+        contents: &str,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<T, Diagnostic> {
+        if self.is_stuck(url) {
+            return Err(internal_compiler_error(
+                contents,
+                "a previous run of the compiler for this file has not finished yet",
+            ));
+        }
+
+        let finished = Arc::new(AtomicBool::new(false));
+        let task = tokio::task::spawn_blocking({
+            let stuck = Arc::clone(&self.stuck);
+            let finished = Arc::clone(&finished);
+            let url = url.clone();
+            move || {
+                let result = f();
+                finished.store(true, Ordering::SeqCst);
+                if let Ok(mut stuck) = stuck.lock() {
+                    stuck.remove(&url);
+                }
+                result
+            }
+        });
+
+        match tokio::time::timeout(COMPILER_TIME_BUDGET, task).await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(e)) => Err(internal_compiler_error(
+                contents,
+                format!("the compiler exited without any result: {e}"),
+            )),
+            Err(_) => {
+                // NOTE: The run may finish right as we time out, in which case
+                // it may have already tried to unmark the document as stuck,
+                // so we check again while holding the lock to never leave it marked
+                if let Ok(mut stuck) = self.stuck.lock() {
+                    if !finished.load(Ordering::SeqCst) {
+                        stuck.insert(url.clone());
+                    }
+                }
+                Err(internal_compiler_error(
+                    contents,
+                    format!(
+                        "the compiler did not finish within {} seconds",
+                        COMPILER_TIME_BUDGET.as_secs()
+                    ),
+                ))
+            }
+        }
+    }
+
+    fn is_stuck(&self, url: &Url) -> bool {
+        self.stuck
+            .lock()
+            .map(|stuck| stuck.contains(url))
+            .unwrap_or_default()
+    }
+}
@@ -1,5 +1,5 @@
 use async_language_server::{
-    lsp_types::{CompletionItemKind, Position},
+    lsp_types::{CompletionItem, CompletionItemKind, Position},
     server::Document,
     tree_sitter::Node,
     tree_sitter_utils::{find_child, ts_range_contains_lsp_position},
@@ -7,9 +7,11 @@ use async_language_server::{
 
 use crate::utils::is_namespace;
 
+use super::completion_item;
+
 const KEYWORDS: [&str; 5] = ["type", "opt", "event", "funct", "namespace"];
 
-pub fn completion(_doc: &Document, pos: Position, node: Node) -> Vec<(CompletionItemKind, String)> {
+pub fn completion(_doc: &Document, pos: Position, node: Node) -> Vec<CompletionItem> {
     // If our current node is a top-level, we can probably
     // find something that is a bit more specific & useful
     let node = if is_namespace(node) {
@@ -35,7 +37,7 @@ pub fn completion(_doc: &Document, pos: Position, node: Node) -> Vec<(Completion
         items.extend(
             KEYWORDS
                 .iter()
-                .map(|word| (CompletionItemKind::KEYWORD, (*word).to_string())),
+                .map(|word| completion_item(CompletionItemKind::KEYWORD, (*word).to_string())),
         );
     }
 
@@ -1,5 +1,9 @@
 use async_language_server::{
-    lsp_types::Position, server::Document, tree_sitter::Node,
+    lsp_types::{
+        CompletionItem, CompletionItemKind, Documentation, MarkupContent, MarkupKind, Position,
+    },
+    server::Document,
+    tree_sitter::Node,
     tree_sitter_utils::ts_point_to_lsp_position,
 };
 use zap_language::tree_sitter_utils::AtomIterator;
@@ -36,6 +40,37 @@ pub fn completion_trigger_characters() -> Vec<String> {
     chars
 }
 
+/**
+    Creates a plain completion item with the given kind and label.
+*/
+fn completion_item(kind: CompletionItemKind, label: String) -> CompletionItem {
+    CompletionItem {
+        kind: Some(kind),
+        label,
+        ..Default::default()
+    }
+}
+
+/**
+    Creates a completion item with the given kind and label,
+    and the given Markdown documentation, such as a doc comment.
+*/
+fn completion_item_with_docs(
+    kind: CompletionItemKind,
+    label: String,
+    docs: Option<String>,
+) -> CompletionItem {
+    CompletionItem {
+        documentation: docs.map(|value| {
+            Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            })
+        }),
+        ..completion_item(kind, label)
+    }
+}
+
 /**
     Modifies the given position to be more accurate for completions.
 
@@ -1,16 +1,17 @@
 use async_language_server::{
-    lsp_types::{CompletionItemKind, Position},
+    lsp_types::{CompletionItem, CompletionItemKind, Position},
     server::Document,
     tree_sitter::Node,
     tree_sitter_utils::{find_child, find_descendant, ts_range_contains_lsp_position},
 };
 
-use crate::{
-    structs::ReferencedType,
-    utils::{is_namespace, is_type},
-};
+use zap_analysis::{ReferencedType, Source};
+
+use crate::utils::{doc_comment, is_namespace, is_type, source};
 
-pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionItemKind, String)> {
+use super::completion_item_with_docs;
+
+pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<CompletionItem> {
     // If our current node is a top-level, we can probably
     // find something that is a bit more specific & useful
     let node = if is_namespace(node) {
@@ -29,13 +30,16 @@ pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionI
     .unwrap_or(node);
 
     let mut items = Vec::new();
+    let Some(src) = source(doc) else {
+        return items;
+    };
 
     if let Some(typ) = node.parent().and_then(ReferencedType::from_node) {
         if ts_range_contains_lsp_position(typ.identifier_range(), pos) {
             // Node is C in a namespaced type like A.B.C, we should resolve
             // whatever namespace B is, and add completions for types in it
-            if let Some(final_namespace) = typ.resolve_namespace(doc, None) {
-                items.extend(completions_in_namespace(doc, final_namespace));
+            if let Some(final_namespace) = typ.resolve_namespace(&src, None) {
+                items.extend(completions_in_namespace(&src, final_namespace));
             }
         } else if node.is_named() {
             // Node is A/B in a namespaced type like A.B.C, we should resolve
@@ -45,8 +49,8 @@ pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionI
                 .children_by_field_name("namespace", &mut parent.walk())
                 .position(|child| child == node)
                 .expect("node is child of its own parent, and is a 'namespace' field");
-            if let Some(final_namespace) = typ.resolve_namespace(doc, Some(index)) {
-                items.extend(completions_in_namespace(doc, final_namespace));
+            if let Some(final_namespace) = typ.resolve_namespace(&src, Some(index)) {
+                items.extend(completions_in_namespace(&src, final_namespace));
             }
         }
     }
@@ -54,7 +58,7 @@ pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionI
     items
 }
 
-fn completions_in_namespace(doc: &Document, namespace: Node) -> Vec<(CompletionItemKind, String)> {
+fn completions_in_namespace(src: &Source, namespace: Node) -> Vec<CompletionItem> {
     let mut items = Vec::new();
     let mut cursor = namespace.walk();
 
@@ -65,13 +69,17 @@ fn completions_in_namespace(doc: &Document, namespace: Node) -> Vec<(CompletionI
             _ => continue,
         };
         if let Some(name) = child.child_by_field_name("name") {
-            let text = doc.node_text(name);
+            let text = src.node_text(name);
             let kind = if is_namespace {
                 CompletionItemKind::MODULE
             } else {
                 CompletionItemKind::VARIABLE
             };
-            items.push((kind, text));
+            items.push(completion_item_with_docs(
+                kind,
+                text,
+                doc_comment(src, child),
+            ));
         }
     }
 
@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use async_language_server::{
-    lsp_types::{CompletionItemKind, Position, Url},
+    lsp_types::{CompletionItem, CompletionItemKind, Position, Url},
     server::Document,
     tree_sitter::Node,
     tree_sitter_utils::{find_child, find_nearest, ts_range_contains_lsp_position},
@@ -9,11 +9,9 @@ use async_language_server::{
 
 use zap_language::docs::{find_option, find_variants, get_option_names};
 
-pub async fn completion(
-    doc: &Document,
-    pos: Position,
-    node: Node<'_>,
-) -> Vec<(CompletionItemKind, String)> {
+use super::completion_item;
+
+pub async fn completion(doc: &Document, pos: Position, node: Node<'_>) -> Vec<CompletionItem> {
     let Some(node) = find_nearest(node, pos, |n| n.kind() == "option_declaration") else {
         return Vec::new();
     };
@@ -32,7 +30,10 @@ pub async fn completion(
 
     if ts_range_contains_lsp_position(opt_ident.range(), pos) {
         // We are currently inside the identifier, complete option names
-        items.extend(get_option_names().map(|opt| (CompletionItemKind::PROPERTY, opt.to_string())));
+        items.extend(
+            get_option_names()
+                .map(|opt| completion_item(CompletionItemKind::PROPERTY, opt.to_string())),
+        );
     } else if opt_value.is_some_and(|v| ts_range_contains_lsp_position(v.range(), pos)) {
         // We are currently inside the value, try to complete variants
         let Some(opt_value) = opt_value else {
@@ -42,15 +43,21 @@ pub async fn completion(
             if typ == "boolean" {
                 // Plain booleans - will be categorized as "identifier" when incomplete
                 if matches!(opt_value.kind(), "boolean" | "identifier") {
-                    items.push((CompletionItemKind::CONSTANT, String::from("true")));
-                    items.push((CompletionItemKind::CONSTANT, String::from("false")));
+                    items.push(completion_item(
+                        CompletionItemKind::CONSTANT,
+                        String::from("true"),
+                    ));
+                    items.push(completion_item(
+                        CompletionItemKind::CONSTANT,
+                        String::from("false"),
+                    ));
                 }
             } else if typ == "variant" {
                 // Option variants - must also be enclosed in strings
                 if opt_value.kind() == "string" {
                     if let Some((true, variants)) = find_variants([&opt_name]) {
                         items.extend(variants.iter().map(|variant| {
-                            (CompletionItemKind::ENUM_MEMBER, (*variant).to_string())
+                            completion_item(CompletionItemKind::ENUM_MEMBER, (*variant).to_string())
                         }));
                     }
                 }
@@ -69,7 +76,9 @@ pub async fn completion(
                             .await
                             .into_iter()
                             .flatten()
-                            .map(|variant| (CompletionItemKind::FOLDER, variant.to_string())),
+                            .map(|variant| {
+                                completion_item(CompletionItemKind::FOLDER, variant.to_string())
+                            }),
                     );
                 }
             } else if typ == "number" {
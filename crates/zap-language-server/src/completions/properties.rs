@@ -1,5 +1,5 @@
 use async_language_server::{
-    lsp_types::{CompletionItemKind, Position},
+    lsp_types::{CompletionItem, CompletionItemKind, Position},
     server::Document,
     tree_sitter::Node,
     tree_sitter_utils::{find_child, find_descendant, ts_range_contains_lsp_position},
@@ -11,7 +11,9 @@ use zap_language::{
 
 use crate::utils::is_namespace;
 
-pub fn completion(_doc: &Document, pos: Position, node: Node) -> Vec<(CompletionItemKind, String)> {
+use super::completion_item;
+
+pub fn completion(_doc: &Document, pos: Position, node: Node) -> Vec<CompletionItem> {
     // If our current node is a top-level, we can probably
     // find something that is a bit more specific & useful
     let node = if is_namespace(node) {
@@ -40,7 +42,8 @@ pub fn completion(_doc: &Document, pos: Position, node: Node) -> Vec<(Completion
             .is_some_and(|d| ts_range_contains_lsp_position(d.range(), pos))
     {
         items.extend(
-            get_property_names().map(|prop| (CompletionItemKind::PROPERTY, prop.to_string())),
+            get_property_names()
+                .map(|prop| completion_item(CompletionItemKind::PROPERTY, prop.to_string())),
         );
     }
 
@@ -48,9 +51,9 @@ pub fn completion(_doc: &Document, pos: Position, node: Node) -> Vec<(Completion
     if let Some(parent) = node.parent().filter(|n| is_field_node(*n)) {
         if let Some((false, variants)) = find_variants([parent.kind()]) {
             items.extend(
-                variants
-                    .iter()
-                    .map(|word| (CompletionItemKind::ENUM_MEMBER, (*word).to_string())),
+                variants.iter().map(|word| {
+                    completion_item(CompletionItemKind::ENUM_MEMBER, (*word).to_string())
+                }),
             );
         }
     }
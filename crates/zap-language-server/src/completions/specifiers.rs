@@ -1,5 +1,5 @@
 use async_language_server::{
-    lsp_types::{CompletionItemKind, Position},
+    lsp_types::{CompletionItem, CompletionItemKind, Position},
     server::Document,
     tree_sitter::Node,
     tree_sitter_utils::{
@@ -7,11 +7,16 @@ use async_language_server::{
     },
 };
 
-use zap_language::docs::get_instance_class_names;
+use zap_language::{
+    ast::{AstNode, PrimitiveType},
+    docs::get_instance_class_names,
+};
 
 use crate::utils::{is_namespace, is_type_primitive};
 
-pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionItemKind, String)> {
+use super::completion_item;
+
+pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<CompletionItem> {
     // If our current node is a top-level, we can probably
     // find something that is a bit more specific & useful
     let node = if is_namespace(node) {
@@ -49,15 +54,15 @@ pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionI
         SpecifierKind::String => {
             // String specifiers can only be utf8 or binary
             vec![
-                (CompletionItemKind::VALUE, String::from("utf8")),
-                (CompletionItemKind::VALUE, String::from("binary")),
+                completion_item(CompletionItemKind::VALUE, String::from("utf8")),
+                completion_item(CompletionItemKind::VALUE, String::from("binary")),
             ]
         }
         SpecifierKind::Instance => {
             // Return all possible class names and
             // let the editor filter when typing
             get_instance_class_names()
-                .map(|name| (CompletionItemKind::VALUE, name.to_string()))
+                .map(|name| completion_item(CompletionItemKind::VALUE, name.to_string()))
                 .collect()
         }
     }
@@ -70,7 +75,7 @@ enum SpecifierKind {
 }
 
 fn find_specifier_kind<'a>(doc: &Document, node: Node<'a>) -> Option<(Node<'a>, SpecifierKind)> {
-    let child_node = node.child(0)?;
+    let child_node = PrimitiveType::cast(node)?.name()?;
     let child_text = doc.text().byte_slice(child_node.byte_range()).as_str()?;
 
     if child_text == "string" {
@@ -1,19 +1,19 @@
 use async_language_server::{
-    lsp_types::{CompletionItemKind, Position},
+    lsp_types::{CompletionItem, CompletionItemKind, Position},
     server::Document,
     tree_sitter::Node,
     tree_sitter_utils::{
         find_ancestor, find_child, find_descendant, ts_range_contains_lsp_position,
     },
 };
+use zap_analysis::{DeclaredNamespace, DeclaredType};
 use zap_language::docs::get_primitive_names;
 
-use crate::{
-    structs::{DeclaredNamespace, DeclaredType},
-    utils::{is_namespace, is_type},
-};
+use crate::utils::{is_namespace, is_type, source};
+
+use super::{completion_item, completion_item_with_docs};
 
-pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionItemKind, String)> {
+pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<CompletionItem> {
     // If our current node is a top-level, we can probably
     // find something that is a bit more specific & useful
     let node = if is_namespace(node) {
@@ -37,26 +37,41 @@ pub fn completion(doc: &Document, pos: Position, node: Node) -> Vec<(CompletionI
         items.extend(
             ["struct", "enum", "set", "map"]
                 .iter()
-                .map(|word| (CompletionItemKind::KEYWORD, (*word).to_string())),
+                .map(|word| completion_item(CompletionItemKind::KEYWORD, (*word).to_string())),
         );
 
         items.extend(
-            get_primitive_names().map(|prim| (CompletionItemKind::CLASS, prim.to_string())),
+            get_primitive_names()
+                .map(|prim| completion_item(CompletionItemKind::CLASS, prim.to_string())),
         );
 
-        if let Some(nearest_namespace) = find_ancestor(node, is_namespace) {
+        if let (Some(src), Some(nearest_namespace)) =
+            (source(doc), find_ancestor(node, is_namespace))
+        {
             items.extend(
                 DeclaredType::find_all_in(nearest_namespace)
                     .into_iter()
                     .filter(|decl| decl.is_in_namespace(nearest_namespace))
-                    .map(|decl| (CompletionItemKind::VARIABLE, decl.identifier_text(doc))),
+                    .map(|decl| {
+                        completion_item_with_docs(
+                            CompletionItemKind::VARIABLE,
+                            decl.identifier_text(&src),
+                            decl.doc_comment(&src),
+                        )
+                    }),
             );
 
             items.extend(
                 DeclaredNamespace::find_all_in(nearest_namespace)
                     .into_iter()
                     .filter(|decl| decl.is_in_namespace(nearest_namespace))
-                    .map(|decl| (CompletionItemKind::MODULE, decl.identifier_text(doc))),
+                    .map(|decl| {
+                        completion_item_with_docs(
+                            CompletionItemKind::MODULE,
+                            decl.identifier_text(&src),
+                            decl.doc_comment(&src),
+                        )
+                    }),
             );
         }
     }
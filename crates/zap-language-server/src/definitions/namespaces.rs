@@ -2,19 +2,29 @@ use async_language_server::{
     lsp_types::{GotoDefinitionResponse, Location, Position},
     server::Document,
     tree_sitter::Node,
-    tree_sitter_utils::ts_range_to_lsp_range,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
 };
 
-use crate::structs::ReferencedNamespace;
+use crate::model::{SemanticModel, SymbolKind};
 
-pub fn definition(doc: &Document, _pos: Position, node: Node) -> Option<GotoDefinitionResponse> {
+pub fn definition(
+    doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+) -> Option<GotoDefinitionResponse> {
     // For namespace definitions, we want to resolve namespace identifiers
     // within namespaced types to their declarations
-    let ns = ReferencedNamespace::from_node(node)?;
-    let decl = ns.resolve_declaration(doc)?;
+    let occurrence = model
+        .occurrence_at(lsp_position_to_ts_point(pos))
+        .filter(|o| !o.is_declaration)?;
+    let symbol = model.symbol(occurrence.symbol);
+    if symbol.kind != SymbolKind::Namespace {
+        return None;
+    }
 
     Some(GotoDefinitionResponse::Scalar(Location {
         uri: doc.url().clone(),
-        range: ts_range_to_lsp_range(decl.identifier_range()),
+        range: ts_range_to_lsp_range(symbol.identifier),
     }))
 }
@@ -2,25 +2,29 @@ use async_language_server::{
     lsp_types::{GotoDefinitionResponse, Location, Position},
     server::Document,
     tree_sitter::Node,
-    tree_sitter_utils::ts_range_to_lsp_range,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
 };
 
-use crate::structs::ReferencedType;
+use crate::model::{SemanticModel, SymbolKind};
 
-pub fn definition(doc: &Document, _pos: Position, node: Node) -> Option<GotoDefinitionResponse> {
-    // May be a referenced type that needs to be resolved,
-    // if we are selecting a qualified / namespaced type we
-    // should also make sure to resolve the *full* reference
-    let node = match node.parent() {
-        Some(p) if p.kind() == "namespaced_type" => p,
-        _ => node,
-    };
-
-    let typ = ReferencedType::from_node(node)?;
-    let decl = typ.resolve_declaration(doc)?;
+pub fn definition(
+    doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+) -> Option<GotoDefinitionResponse> {
+    // May be a referenced type that has been resolved, qualified / namespaced
+    // types resolve using their final identifier, such as C in A.B.C
+    let occurrence = model
+        .occurrence_at(lsp_position_to_ts_point(pos))
+        .filter(|o| !o.is_declaration)?;
+    let symbol = model.symbol(occurrence.symbol);
+    if symbol.kind != SymbolKind::Type {
+        return None;
+    }
 
     Some(GotoDefinitionResponse::Scalar(Location {
         uri: doc.url().clone(),
-        range: ts_range_to_lsp_range(decl.identifier_range()),
+        range: ts_range_to_lsp_range(symbol.identifier),
     }))
 }
@@ -12,6 +12,10 @@ use async_language_server::{
 
 use zap_language::diagnostics::{Diagnostic, LabelStyle, Severity};
 
+mod unused;
+
+pub use self::unused::{UNUSED_NAMESPACE_CODE, UNUSED_TYPE_CODE, unused_diagnostics};
+
 pub fn zap_diagnostic_to_lsp_diagnostic(
     document: &Document,
     diagnostic: Diagnostic,
@@ -24,8 +28,11 @@ pub fn zap_diagnostic_to_lsp_diagnostic(
     let range = byte_range_to_lsp_range(document, primary_label.range.clone())?;
     let severity = match diagnostic.severity {
         Severity::Help => Some(LspDiagnosticSeverity::HINT),
-        Severity::Bug | Severity::Note => Some(LspDiagnosticSeverity::INFORMATION),
-        Severity::Warning => Some(LspDiagnosticSeverity::WARNING),
+        Severity::Note => Some(LspDiagnosticSeverity::INFORMATION),
+        // Internal compiler errors do not mean that the document is invalid,
+        // but they do mean that errors in it may be missing, so they should
+        // be just as visible as any other problem with the document
+        Severity::Bug | Severity::Warning => Some(LspDiagnosticSeverity::WARNING),
         Severity::Error => Some(LspDiagnosticSeverity::ERROR),
     };
 
@@ -0,0 +1,73 @@
+use async_language_server::{
+    lsp_types::{
+        Diagnostic as LspDiagnostic, DiagnosticSeverity as LspDiagnosticSeverity,
+        DiagnosticTag as LspDiagnosticTag, NumberOrString, Position as LspPosition,
+        Range as LspRange,
+    },
+    server::Document,
+    tree_sitter::Range,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_analysis::unused_declarations;
+
+use crate::{
+    model::{SemanticModel, SymbolKind},
+    utils::source,
+};
+
+pub const UNUSED_TYPE_CODE: &str = "unused-type";
+pub const UNUSED_NAMESPACE_CODE: &str = "unused-namespace";
+
+/**
+    Creates diagnostics for all type and namespace declarations that are never referenced.
+
+    The diagnostic data contains the full range of the declaration to
+    remove, including its line, which is used by the matching quick fix.
+*/
+pub fn unused_diagnostics(doc: &Document, model: &SemanticModel) -> Vec<LspDiagnostic> {
+    let Some(src) = source(doc) else {
+        return Vec::new();
+    };
+
+    unused_declarations(&src, model)
+        .into_iter()
+        .map(|unused| {
+            let (code, noun) = if unused.kind == SymbolKind::Type {
+                (UNUSED_TYPE_CODE, "Type")
+            } else {
+                (UNUSED_NAMESPACE_CODE, "Namespace")
+            };
+            unused_diagnostic(
+                code,
+                format!("{noun} `{}` is never used", unused.name),
+                unused.identifier,
+                unused.declaration,
+            )
+        })
+        .collect()
+}
+
+fn unused_diagnostic(
+    code: &str,
+    message: String,
+    range: Range,
+    declaration: Range,
+) -> LspDiagnostic {
+    // Remove full lines, including indentation and the trailing newline
+    let decl_range = ts_range_to_lsp_range(declaration);
+    let removal_range = LspRange {
+        start: LspPosition::new(decl_range.start.line, 0),
+        end: LspPosition::new(decl_range.end.line + 1, 0),
+    };
+
+    LspDiagnostic {
+        range: ts_range_to_lsp_range(range),
+        severity: Some(LspDiagnosticSeverity::HINT),
+        message,
+        tags: Some(vec![LspDiagnosticTag::UNNECESSARY]),
+        source: Some(String::from("Zap")),
+        code: Some(NumberOrString::String(code.to_string())),
+        data: serde_json::to_value(removal_range).ok(),
+        ..Default::default()
+    }
+}
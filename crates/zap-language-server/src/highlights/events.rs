@@ -0,0 +1,25 @@
+use async_language_server::{
+    lsp_types::{DocumentHighlight, DocumentHighlightKind, Position},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+
+pub fn highlight(_doc: &Document, _pos: Position, node: Node) -> Option<Vec<DocumentHighlight>> {
+    // Events and functions can not be referenced from within a Zap
+    // file, so the only thing to highlight is the declaration itself
+    let parent = node.parent()?;
+    if !matches!(parent.kind(), "event_declaration" | "function_declaration") {
+        return None;
+    }
+
+    let name = parent.child_by_field_name("name")?;
+    if name != node {
+        return None;
+    }
+
+    Some(vec![DocumentHighlight {
+        range: ts_range_to_lsp_range(name.range()),
+        kind: Some(DocumentHighlightKind::WRITE),
+    }])
+}
@@ -0,0 +1,7 @@
+mod events;
+mod namespaces;
+mod types;
+
+pub use self::events::highlight as highlight_for_events;
+pub use self::namespaces::highlight as highlight_for_namespaces;
+pub use self::types::highlight as highlight_for_types;
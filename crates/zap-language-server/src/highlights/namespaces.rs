@@ -0,0 +1,39 @@
+use async_language_server::{
+    lsp_types::{DocumentHighlight, DocumentHighlightKind, Position},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+};
+
+use crate::model::{SemanticModel, SymbolKind};
+
+pub fn highlight(
+    _doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+) -> Option<Vec<DocumentHighlight>> {
+    // 1. Find the namespace declaration, either directly or through a resolved reference
+    let occurrence = model.occurrence_at(lsp_position_to_ts_point(pos))?;
+    let symbol = model.symbol(occurrence.symbol);
+    if symbol.kind != SymbolKind::Namespace {
+        return None;
+    }
+
+    // 2. The declaration is written to, all of the references are read from
+    let mut highlights = vec![DocumentHighlight {
+        range: ts_range_to_lsp_range(symbol.identifier),
+        kind: Some(DocumentHighlightKind::WRITE),
+    }];
+
+    highlights.extend(
+        model
+            .references_to(occurrence.symbol)
+            .map(|ns| DocumentHighlight {
+                range: ts_range_to_lsp_range(ns.identifier),
+                kind: Some(DocumentHighlightKind::READ),
+            }),
+    );
+
+    Some(highlights)
+}
@@ -0,0 +1,179 @@
+use async_language_server::{
+    lsp_types::{Hover, HoverContents, MarkedString, Position},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+};
+use zap_language::{
+    ast::{AstNode, EventDecl, FieldDecl, FunctDecl},
+    constants::PROPERTY_DEFINITIONS,
+    docs::find_variants,
+};
+
+use crate::{
+    model::{SemanticModel, SymbolKind},
+    utils::{api_path, apply_casing, doc_comment, find_option_value, source},
+};
+
+const PAYLOAD_FIELDS: [&str; 3] = [
+    "event_data_field",
+    "function_args_field",
+    "function_rets_field",
+];
+
+/**
+    Shows a summary of an event or function declaration when hovering its name,
+    including all of its fields, its payload, and its generated API.
+
+    ### Example
+
+    ```zap
+    event PlayerJoined = {
+        from: Server,
+        type: Reliable,
+        data: string,
+    }
+    ```
+
+    Would list `from`, `type` and the effective `call` mode, the payload,
+    and that the server fires it using `Fire` / `FireAll` / ..., while
+    the client listens to it using `On`, or `SetCallback` / `Iter`.
+*/
+pub fn hover(doc: &Document, model: &SemanticModel, pos: Position, _node: Node) -> Option<Hover> {
+    let occurrence = model
+        .occurrence_at(lsp_position_to_ts_point(pos))
+        .filter(|o| o.is_declaration)?;
+    let symbol = model.symbol(occurrence.symbol);
+    if !matches!(symbol.kind, SymbolKind::Event | SymbolKind::Function) {
+        return None;
+    }
+    let src = source(doc)?;
+    let decl = model.declaration_node(&src, occurrence.symbol)?;
+
+    let is_event = symbol.kind == SymbolKind::Event;
+    let keyword = if is_event { "event" } else { "funct" };
+    let prefix = if is_event { "event_" } else { "function_" };
+    let fields = declaration_fields(decl);
+
+    let mut contents = format!("```zap\n{keyword} {}\n```\n\n", symbol.name);
+    if let Some(docs) = doc_comment(&src, decl) {
+        contents.push_str(&format!("{docs}\n\n"));
+    }
+
+    // 1. All of the enum-like fields, such as `from`, `type` and `call`
+    let mut call = None;
+    for (kind, header, _) in PROPERTY_DEFINITIONS {
+        if !kind.starts_with(prefix) || PAYLOAD_FIELDS.contains(kind) {
+            continue;
+        }
+        let explicit = field_value(doc, &fields, kind);
+        let default = (*kind == "event_call_field" && explicit.is_none())
+            .then(|| find_option_value(doc, "call_default"))
+            .flatten();
+        let value = match (&explicit, &default) {
+            (Some(value), _) => value.clone(),
+            (None, Some(default)) => format!("{default} *(from `opt call_default`)*"),
+            (None, None) => String::from("*missing*"),
+        };
+        if *kind == "event_call_field" {
+            call = explicit.or(default);
+        }
+        contents.push_str(&format!("- **{header}**: {value}\n"));
+    }
+
+    // 2. The payload, formatted the same way as type declarations
+    for (kind, header, _) in PROPERTY_DEFINITIONS {
+        if !kind.starts_with(prefix) || !PAYLOAD_FIELDS.contains(kind) {
+            continue;
+        }
+        let Some(value) = find_field(&fields, kind).and_then(|field| field.value()) else {
+            continue;
+        };
+        let config = zap_formatter::Config::new(src.text().as_bytes());
+        let mut formatted = String::new();
+        if zap_formatter::format_root(&mut formatted, config, value).is_err() {
+            formatted = src.node_text(value);
+        }
+        contents.push_str(&format!("\n**{header}**\n\n```zap\n{formatted}\n```\n"));
+    }
+
+    // 3. The generated API, for both the server and the client
+    let (server, client) = if is_event {
+        let from = field_value(doc, &fields, "event_from_field");
+        let listener = listener_member(call.as_deref());
+        let fire_all = find_option_value(doc, "disable_fire_all").is_none_or(|v| v != "true");
+        let firing = ["Fire", "FireAll", "FireExcept", "FireList", "FireSet"]
+            .into_iter()
+            .filter(|member| fire_all || *member != "FireAll")
+            .collect::<Vec<_>>();
+        match from.as_deref() {
+            Some("Server") => (firing, vec![listener]),
+            Some("Client") => (vec![listener], vec!["Fire"]),
+            _ => (Vec::new(), Vec::new()),
+        }
+    } else {
+        (vec!["SetCallback"], vec!["Call"])
+    };
+
+    if !server.is_empty() || !client.is_empty() {
+        let path = api_path(doc, decl);
+        let casing = find_option_value(doc, "casing");
+        let describe = |members: Vec<&str>| {
+            members
+                .into_iter()
+                .map(|member| format!("`{path}.{}`", apply_casing(member, casing.as_deref())))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        contents.push_str("\n**Generated API**\n\n");
+        contents.push_str(&format!("- **Server**: {}\n", describe(server)));
+        contents.push_str(&format!("- **Client**: {}\n", describe(client)));
+    }
+
+    Some(Hover {
+        range: Some(ts_range_to_lsp_range(symbol.identifier)),
+        contents: HoverContents::Scalar(MarkedString::String(contents)),
+    })
+}
+
+/**
+    Returns all fields of the given event or function declaration.
+*/
+fn declaration_fields(decl: Node) -> Vec<FieldDecl> {
+    EventDecl::cast(decl)
+        .map(|event| event.fields())
+        .or_else(|| FunctDecl::cast(decl).map(|funct| funct.fields()))
+        .unwrap_or_default()
+}
+
+/**
+    Finds the field of the given kind, such as `event_call_field`, if it exists.
+*/
+fn find_field<'a>(fields: &[FieldDecl<'a>], kind: &str) -> Option<FieldDecl<'a>> {
+    fields
+        .iter()
+        .find(|field| field.syntax().kind() == kind)
+        .copied()
+}
+
+/**
+    Finds the value of an enum-like field in the declaration, if
+    the field exists and its value is one of the known variants.
+*/
+fn field_value(doc: &Document, fields: &[FieldDecl], kind: &str) -> Option<String> {
+    let value = find_field(fields, kind)?.value()?;
+    let value = doc.node_text(value);
+    let (_, variants) = find_variants([kind])?;
+    variants.contains(&value.as_str()).then_some(value)
+}
+
+/**
+    Returns the generated API member used to listen to an event with the given call mode.
+*/
+fn listener_member(call: Option<&str>) -> &'static str {
+    match call {
+        Some("SingleAsync" | "SingleSync") => "SetCallback",
+        Some("Polling") => "Iter",
+        _ => "On",
+    }
+}
@@ -1,9 +1,16 @@
+mod declarations;
 mod keywords;
+mod namespaces;
 mod options;
+mod payloads;
+mod preview;
 mod properties;
 mod types;
 
+pub use self::declarations::hover as hover_for_declarations;
 pub use self::keywords::hover as hover_for_keywords;
+pub use self::namespaces::hover as hover_for_namespaces;
 pub use self::options::hover as hover_for_options;
+pub use self::payloads::hover as hover_for_payloads;
 pub use self::properties::hover as hover_for_properties;
 pub use self::types::hover as hover_for_types;
@@ -0,0 +1,35 @@
+use async_language_server::{
+    lsp_types::{Hover, HoverContents, MarkedString, Position},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+};
+
+use crate::{
+    model::{SemanticModel, SymbolKind},
+    utils::{doc_comment, source},
+};
+
+pub fn hover(doc: &Document, model: &SemanticModel, pos: Position, _node: Node) -> Option<Hover> {
+    // Namespace declarations are hovered using their name, namespace
+    // references using the identifier of the namespace in the path
+    let occurrence = model.occurrence_at(lsp_position_to_ts_point(pos))?;
+    if model.symbol(occurrence.symbol).kind != SymbolKind::Namespace {
+        return None;
+    }
+
+    let path = model.qualified_name(occurrence.symbol);
+    let mut contents = format!("```zap\nnamespace {path}\n```\n");
+    let src = source(doc)?;
+    if let Some(docs) = model
+        .declaration_node(&src, occurrence.symbol)
+        .and_then(|decl| doc_comment(&src, decl))
+    {
+        contents.push_str(&format!("\n{docs}\n"));
+    }
+
+    Some(Hover {
+        range: Some(ts_range_to_lsp_range(occurrence.range)),
+        contents: HoverContents::Scalar(MarkedString::String(contents)),
+    })
+}
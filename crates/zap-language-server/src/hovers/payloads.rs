@@ -0,0 +1,52 @@
+use async_language_server::{
+    lsp_types::{Hover, HoverContents, MarkedString, Position},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{ts_range_contains_lsp_position, ts_range_to_lsp_range},
+};
+use zap_language::{
+    ast::{AstNode, FieldDecl},
+    docs::find_property,
+    tree_sitter_utils::AncestorIterator,
+};
+
+use crate::compiler::Compiler;
+
+use super::preview::generated_type_section;
+
+pub async fn hover(
+    doc: &Document,
+    compiler: &Compiler,
+    pos: Position,
+    node: Node<'_>,
+) -> Option<Hover> {
+    // Find the event data, or function args / rets field we are in
+    let field = AncestorIterator::new(node)
+        .filter_map(FieldDecl::cast)
+        .find(|f| matches!(f.key(), "data" | "args" | "rets"))?;
+
+    // Only hovering the property name itself should show the payload,
+    // any user types inside of the value have their own type hovers
+    let keyword = field.key_node()?;
+    if !ts_range_contains_lsp_position(keyword.range(), pos) {
+        return None;
+    }
+
+    let (_, head, desc) = find_property([field.syntax().kind()])?;
+    let mut contents = format!("# {head}\n\n{desc}\n");
+
+    let value = field.value();
+    let section = match value {
+        Some(value) => generated_type_section(doc, compiler, value).await,
+        None => None,
+    };
+    if let Some(section) = section {
+        contents.push('\n');
+        contents.push_str(&section);
+    }
+
+    Some(Hover {
+        range: Some(ts_range_to_lsp_range(keyword.range())),
+        contents: HoverContents::Scalar(MarkedString::String(contents)),
+    })
+}
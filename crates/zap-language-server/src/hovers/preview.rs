@@ -0,0 +1,84 @@
+use async_language_server::{server::Document, tree_sitter::Node};
+
+use crate::{
+    compiler::Compiler,
+    utils::{find_option_value, qualified_type_text, source},
+};
+
+/**
+    Creates a Markdown section with the generated Luau type, and TypeScript
+    type if the `typescript` option is enabled, for the given type node.
+
+    Tuples, such as function arguments, are previewed one value at a time,
+    since they can not be used as standalone types in a Zap file.
+*/
+pub async fn generated_type_section(
+    doc: &Document,
+    compiler: &Compiler,
+    value: Node<'_>,
+) -> Option<String> {
+    let src = source(doc)?;
+    let root = src.root();
+
+    // Gather all types to preview, and their names if they are tuple values
+    let mut names = Vec::new();
+    let mut types = Vec::new();
+    if value.kind() == "tuple" {
+        let mut cursor = value.walk();
+        for tuple_value in value.named_children(&mut cursor) {
+            let typ = tuple_value.child_by_field_name("type")?;
+            names.push(
+                tuple_value
+                    .child_by_field_name("name")
+                    .map(|name| src.node_text(name)),
+            );
+            types.push(qualified_type_text(&src, typ, root)?);
+        }
+    } else {
+        names.push(None);
+        types.push(qualified_type_text(&src, value, root)?);
+    }
+
+    let previews = compiler
+        .preview_types(doc.url(), src.text().to_string(), types)
+        .await?;
+    let is_tuple = value.kind() == "tuple";
+    let join = |items: Vec<String>| {
+        if is_tuple {
+            format!("({})", items.join(", "))
+        } else {
+            items.join("")
+        }
+    };
+    let with_name = |name: &Option<String>, typ: &str| match name {
+        Some(name) => format!("{name}: {typ}"),
+        None => typ.to_string(),
+    };
+
+    let luau = join(
+        names
+            .iter()
+            .zip(&previews)
+            .map(|(name, preview)| with_name(name, &preview.luau))
+            .collect(),
+    );
+
+    let mut section = format!("---\n\n**Luau**\n\n```luau\n{luau}\n```\n");
+
+    let is_typescript = find_option_value(doc, "typescript").is_some_and(|v| v == "true");
+    if is_typescript {
+        let typescript = names
+            .iter()
+            .zip(&previews)
+            .map(|(name, preview)| Some(with_name(name, preview.typescript.as_deref()?)))
+            .collect::<Option<Vec<_>>>();
+        if let Some(typescript) = typescript {
+            section.push_str(&format!(
+                "\n**TypeScript**\n\n```ts\n{}\n```\n",
+                join(typescript)
+            ));
+        }
+    }
+
+    Some(section)
+}
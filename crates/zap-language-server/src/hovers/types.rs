@@ -2,13 +2,27 @@ use async_language_server::{
     lsp_types::{Hover, HoverContents, MarkedString, Position},
     server::Document,
     tree_sitter::Node,
-    tree_sitter_utils::{find_ancestor, ts_range_to_lsp_range},
+    tree_sitter_utils::{find_ancestor, lsp_position_to_ts_point, ts_range_to_lsp_range},
 };
 use zap_language::docs::find_primitive;
 
-use crate::{structs::ReferencedType, utils::is_type_primitive};
+use zap_analysis::DeclaredType;
 
-pub fn hover(doc: &Document, _pos: Position, node: Node) -> Option<Hover> {
+use crate::{
+    compiler::Compiler,
+    model::{SemanticModel, SymbolKind},
+    utils::{is_type_primitive, source},
+};
+
+use super::preview::generated_type_section;
+
+pub async fn hover(
+    doc: &Document,
+    model: &SemanticModel,
+    compiler: &Compiler,
+    pos: Position,
+    node: Node<'_>,
+) -> Option<Hover> {
     // If we are inside a descendant node of a primitive
     // type, we should traverse up to the main type node
     let node = find_ancestor(node, |a| is_type_primitive(a)).unwrap_or(node);
@@ -26,32 +40,44 @@ pub fn hover(doc: &Document, _pos: Position, node: Node) -> Option<Hover> {
             ))),
         })
     } else {
-        // May be a referenced type that needs to be resolved,
-        // if we are hovering over a qualified / namespaced type
-        // we should also make sure to resolve the *full* reference
-        let node = match node.parent() {
-            Some(p) if p.kind() == "namespaced_type" => p,
-            _ => node,
-        };
-
-        let typ = ReferencedType::from_node(node)?;
-        let decl = typ.resolve_declaration(doc)?;
+        // May be the name of a type declaration itself, or a resolved type
+        // reference, where qualified / namespaced types cover the *full* reference
+        let occurrence = model.occurrence_at(lsp_position_to_ts_point(pos))?;
+        if model.symbol(occurrence.symbol).kind != SymbolKind::Type {
+            return None;
+        }
+        let src = source(doc)?;
+        let decl = model
+            .declaration_node(&src, occurrence.symbol)
+            .and_then(DeclaredType::from_node)?;
+        let range = occurrence.range;
 
         // We show an auto-formatted version of the type declaration
         // here to automatically de-indent and make it easier to read
-        let text = doc.text_bytes();
-        let config = zap_formatter::Config::new(text.as_slice());
+        let config = zap_formatter::Config::new(src.text().as_bytes());
 
         let mut formatted = String::new();
         if zap_formatter::format_root(&mut formatted, config, *decl.as_ref()).is_err() {
-            formatted = decl.declaration_text(doc);
+            formatted = decl.declaration_text(&src);
+        }
+
+        let mut contents = format!("```zap\n{formatted}\n```\n");
+        if let Some(docs) = decl.doc_comment(&src) {
+            contents.push_str(&format!("\n{docs}\n"));
+        }
+        let value = decl.as_ref().child_by_field_name("value");
+        let section = match value {
+            Some(value) => generated_type_section(doc, compiler, value).await,
+            None => None,
+        };
+        if let Some(section) = section {
+            contents.push('\n');
+            contents.push_str(&section);
         }
 
         Some(Hover {
-            range: Some(ts_range_to_lsp_range(typ.reference_range())),
-            contents: HoverContents::Scalar(MarkedString::String(format!(
-                "```zap\n{formatted}\n```",
-            ))),
+            range: Some(ts_range_to_lsp_range(range)),
+            contents: HoverContents::Scalar(MarkedString::String(contents)),
         })
     }
 }
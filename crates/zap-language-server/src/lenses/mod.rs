@@ -0,0 +1,5 @@
+mod references;
+
+pub use self::references::lenses as lenses_for_references;
+pub use self::references::resolve as resolve_lens_for_references;
+pub use self::references::url as lens_url_for_references;
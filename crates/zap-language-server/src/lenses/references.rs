@@ -0,0 +1,141 @@
+use async_language_server::{
+    lsp_types::{
+        CodeLens, Command, Location, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    },
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{ts_point_to_lsp_position, ts_range_to_lsp_range},
+};
+
+use crate::{
+    model::SemanticModel,
+    references::{
+        GeneratedApiAccess, references_for_namespaces, references_for_types, usages_for_events,
+    },
+    workspace::Workspace,
+};
+
+/**
+    The client-side command that opens the references view.
+
+    Arguments are the document uri, the position, and a list of locations.
+*/
+pub const SHOW_REFERENCES_COMMAND: &str = "zap.showReferences";
+
+/**
+    Creates unresolved reference count lenses for all type, namespace,
+    event and function declarations in the given document.
+
+    These lenses must be resolved using [`resolve`] before being shown,
+    which lets us skip resolving references for lenses that are not visible.
+*/
+pub fn lenses(doc: &Document) -> Vec<CodeLens> {
+    let Some(root) = doc.node_at_root() else {
+        return Vec::new();
+    };
+
+    let mut lenses = Vec::new();
+    collect_lenses(doc, root, &mut lenses);
+    lenses
+}
+
+fn collect_lenses(doc: &Document, namespace: Node, lenses: &mut Vec<CodeLens>) {
+    let mut cursor = namespace.walk();
+    for child in namespace.children(&mut cursor) {
+        if !matches!(
+            child.kind(),
+            "type_declaration"
+                | "namespace_declaration"
+                | "event_declaration"
+                | "function_declaration"
+        ) {
+            continue;
+        }
+
+        if let Some(name) = child.child_by_field_name("name") {
+            let params = TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: doc.url().clone(),
+                },
+                position: ts_point_to_lsp_position(name.range().start_point),
+            };
+            lenses.push(CodeLens {
+                range: ts_range_to_lsp_range(name.range()),
+                command: None,
+                data: serde_json::to_value(params).ok(),
+            });
+        }
+
+        if child.kind() == "namespace_declaration" {
+            collect_lenses(doc, child, lenses);
+        }
+    }
+}
+
+/**
+    Returns the url of the document that a lens created using [`lenses`] belongs to.
+*/
+pub fn url(lens: &CodeLens) -> Option<Url> {
+    lens.data
+        .clone()
+        .and_then(|d| serde_json::from_value::<TextDocumentPositionParams>(d).ok())
+        .map(|params| params.text_document.uri)
+}
+
+/**
+    Resolves the reference count and command for a lens created using [`lenses`].
+
+    Events and functions are never referenced from within Zap files, so their
+    lenses instead count usages of the generated API throughout the workspace.
+*/
+pub async fn resolve(
+    doc: &Document,
+    model: &SemanticModel,
+    workspace: &Workspace,
+    lens: CodeLens,
+) -> CodeLens {
+    let Some(params) = lens
+        .data
+        .clone()
+        .and_then(|d| serde_json::from_value::<TextDocumentPositionParams>(d).ok())
+    else {
+        return lens;
+    };
+
+    let pos = params.position;
+    let node = doc.node_at_position_named(pos);
+    let access = node.and_then(|node| GeneratedApiAccess::from_node(doc, node));
+
+    let mut locations = node
+        .and_then(|node| {
+            references_for_namespaces(doc, model, pos, node)
+                .or_else(|| references_for_types(doc, model, pos, node))
+        })
+        .unwrap_or_default();
+    if let Some(access) = access {
+        locations = usages_for_events(workspace, &access).await;
+    }
+
+    let title = match locations.len() {
+        1 => String::from("1 reference"),
+        n => format!("{n} references"),
+    };
+
+    let arguments = [
+        serde_json::to_value(&params.text_document.uri),
+        serde_json::to_value(pos),
+        serde_json::to_value::<&[Location]>(&locations),
+    ]
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .ok();
+
+    CodeLens {
+        command: Some(Command {
+            title,
+            command: String::from(SHOW_REFERENCES_COMMAND),
+            arguments,
+        }),
+        ..lens
+    }
+}
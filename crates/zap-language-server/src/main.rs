@@ -1,14 +1,24 @@
+mod actions;
 mod cli;
+mod codegen;
+mod compiler;
 mod completions;
 mod definitions;
 mod diagnostics;
+mod highlights;
 mod hovers;
+mod lenses;
+mod model;
+mod ranges;
 mod references;
 mod renames;
+mod semantic_tokens;
 mod server;
-mod structs;
+mod settings;
+mod symbols;
 mod tracing;
 mod utils;
+mod workspace;
 
 use self::tracing::setup_tracing;
 
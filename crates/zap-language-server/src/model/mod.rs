@@ -0,0 +1,77 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+use async_language_server::{lsp_types::Url, server::Document};
+
+use crate::utils::source;
+
+pub use zap_analysis::{Reference, SemanticModel, Symbol, SymbolId, SymbolKind};
+
+/**
+    A cache of the most recently built semantic model for each document.
+
+    Models are built at most once per document version, where versions
+    are identified by the full document contents, and any requests for
+    the same version share the same model.
+
+    Cheap to clone, all clones share the same underlying cache.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct SemanticModelCache {
+    models: Arc<Mutex<HashMap<Url, (u64, Arc<SemanticModel>)>>>,
+}
+
+impl SemanticModelCache {
+    /**
+        Returns the semantic model for the current version of the
+        given document, building and caching it if necessary.
+    */
+    pub fn get(&self, doc: &Document) -> Arc<SemanticModel> {
+        let version = document_version(doc);
+
+        let cached = self.models.lock().ok().and_then(|models| {
+            models
+                .get(doc.url())
+                .filter(|(v, _)| *v == version)
+                .map(|(_, model)| Arc::clone(model))
+        });
+        if let Some(model) = cached {
+            return model;
+        }
+
+        // Build the model without holding the lock, so that
+        // requests for other documents are not blocked by it
+        let model = Arc::new(
+            source(doc)
+                .map(|src| SemanticModel::build(&src))
+                .unwrap_or_default(),
+        );
+        if let Ok(mut models) = self.models.lock() {
+            models.insert(doc.url().clone(), (version, Arc::clone(&model)));
+        }
+        model
+    }
+
+    /**
+        Removes the cached model for the given document, such as when it is closed.
+    */
+    pub fn remove(&self, url: &Url) {
+        if let Ok(mut models) = self.models.lock() {
+            models.remove(url);
+        }
+    }
+}
+
+/*
+    Documents do not expose the version numbers sent by the client, so we identify
+    versions using a hash of the contents instead - hashing the rope directly walks
+    its chunks in place, without copying the full contents into a new buffer first
+*/
+fn document_version(doc: &Document) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    doc.text().hash(&mut hasher);
+    hasher.finish()
+}
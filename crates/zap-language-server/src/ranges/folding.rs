@@ -0,0 +1,91 @@
+use async_language_server::{
+    lsp_types::{FoldingRange, FoldingRangeKind},
+    server::Document,
+    tree_sitter::Node,
+};
+use zap_language::tree_sitter_utils::{DepthFirstNodeIterator, is_comment_node};
+
+/**
+    Gathers all folding ranges in the given document.
+
+    Includes all brace-enclosed blocks - namespaces, events, functions,
+    structs, enums, tuples - as well as blocks of consecutive comments.
+*/
+pub fn folding_ranges(doc: &Document) -> Vec<FoldingRange> {
+    let Some(root) = doc.node_at_root() else {
+        return Vec::new();
+    };
+
+    let mut ranges = Vec::new();
+    let mut comment_block: Option<(usize, usize)> = None;
+
+    for node in DepthFirstNodeIterator::new(root) {
+        if is_comment_node(node) {
+            let row = node.range().start_point.row;
+            comment_block = match comment_block {
+                Some((start, end)) if end + 1 == row => Some((start, row)),
+                Some((start, end)) => {
+                    ranges.extend(comment_range(start, end));
+                    Some((row, row))
+                }
+                None => Some((row, row)),
+            };
+        } else if is_foldable(node) {
+            ranges.extend(block_range(node));
+        }
+    }
+
+    if let Some((start, end)) = comment_block {
+        ranges.extend(comment_range(start, end));
+    }
+
+    ranges
+}
+
+fn is_foldable(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "namespace_declaration"
+            | "event_declaration"
+            | "function_declaration"
+            | "struct_type"
+            | "enum_type"
+            | "enum_variant"
+            | "tuple"
+    )
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn block_range(node: Node) -> Option<FoldingRange> {
+    let start = node.range().start_point.row;
+    let end = node.range().end_point.row;
+
+    // Keep the closing brace visible when folded, same as most editors
+    // do with their own indentation-based folding, and ignore any
+    // blocks that would not fold anything away at all when folded
+    let end = end.saturating_sub(1);
+    if end <= start {
+        return None;
+    }
+
+    Some(FoldingRange {
+        start_line: start as u32,
+        end_line: end as u32,
+        kind: Some(FoldingRangeKind::Region),
+        ..Default::default()
+    })
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn comment_range(start: usize, end: usize) -> Option<FoldingRange> {
+    if end <= start {
+        return None;
+    }
+
+    Some(FoldingRange {
+        start_line: start as u32,
+        end_line: end as u32,
+        kind: Some(FoldingRangeKind::Comment),
+        ..Default::default()
+    })
+}
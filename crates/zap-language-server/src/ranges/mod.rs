@@ -0,0 +1,5 @@
+mod folding;
+mod selection;
+
+pub use self::folding::folding_ranges;
+pub use self::selection::selection_ranges;
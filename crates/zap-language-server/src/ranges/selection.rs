@@ -0,0 +1,52 @@
+use async_language_server::{
+    lsp_types::{Position, Range, SelectionRange},
+    server::Document,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_language::tree_sitter_utils::AncestorIterator;
+
+/**
+    Gathers selection ranges for each of the given positions, for smart expand / shrink selection.
+
+    Each selection range starts at the innermost node at the position,
+    and expands outwards through all of its ancestors, until the root.
+*/
+pub fn selection_ranges(doc: &Document, positions: Vec<Position>) -> Vec<SelectionRange> {
+    positions
+        .into_iter()
+        .map(|pos| selection_range(doc, pos))
+        .collect()
+}
+
+fn selection_range(doc: &Document, pos: Position) -> SelectionRange {
+    let Some(node) = doc.node_at_position_named(pos) else {
+        // Clients expect a selection range for every position,
+        // so give back an empty selection if nothing was found
+        return SelectionRange {
+            range: Range::new(pos, pos),
+            parent: None,
+        };
+    };
+
+    // Collect ranges from the innermost to outermost node, skipping any
+    // ancestors that span the exact same range, since those would not
+    // expand the selection at all and just require an extra key press
+    let mut ranges = Vec::new();
+    for ancestor in AncestorIterator::new(node) {
+        let range = ts_range_to_lsp_range(ancestor.range());
+        if ranges.last() != Some(&range) {
+            ranges.push(range);
+        }
+    }
+
+    // Build the linked list from the outermost node, inwards
+    let mut selection: Option<SelectionRange> = None;
+    for range in ranges.into_iter().rev() {
+        selection = Some(SelectionRange {
+            range,
+            parent: selection.map(Box::new),
+        });
+    }
+
+    selection.expect("ancestor iterator always yields at least the node itself")
+}
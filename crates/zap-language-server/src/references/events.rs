@@ -0,0 +1,31 @@
+use async_language_server::{
+    lsp_types::{Location, Position},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+};
+
+use crate::model::{SemanticModel, SymbolKind};
+
+pub fn references(
+    doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+) -> Option<Vec<Location>> {
+    // 1. Make sure we are on the name of an event or function declaration
+    let occurrence = model
+        .occurrence_at(lsp_position_to_ts_point(pos))
+        .filter(|o| o.is_declaration)?;
+    let symbol = model.symbol(occurrence.symbol);
+    if !matches!(symbol.kind, SymbolKind::Event | SymbolKind::Function) {
+        return None;
+    }
+
+    // 2. Events and functions can not be referenced from within a Zap
+    //    file, so the only location within it is the declaration itself
+    Some(vec![Location {
+        uri: doc.url().clone(),
+        range: ts_range_to_lsp_range(symbol.identifier),
+    }])
+}
@@ -1,5 +1,10 @@
+mod events;
 mod namespaces;
 mod types;
+mod usages;
 
+pub use self::events::references as references_for_events;
 pub use self::namespaces::references as references_for_namespaces;
 pub use self::types::references as references_for_types;
+
+pub use self::usages::{FileScan, GeneratedApiAccess, usages as usages_for_events};
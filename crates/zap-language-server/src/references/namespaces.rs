@@ -2,36 +2,30 @@ use async_language_server::{
     lsp_types::{Location, Position},
     server::Document,
     tree_sitter::Node,
-    tree_sitter_utils::ts_range_to_lsp_range,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
 };
 
-use crate::structs::{DeclaredNamespace, ReferencedNamespace};
+use crate::model::{SemanticModel, SymbolKind};
 
-pub fn references(doc: &Document, _pos: Position, node: Node) -> Option<Vec<Location>> {
-    // 1. Transform the identifier node we are possibly on, into the
-    //    full node for the declaration / reference, when possible
-    let node = match node.parent() {
-        Some(p) if matches!(p.kind(), "namespace_declaration" | "namespaced_type") => p,
-        _ => node,
-    };
+pub fn references(
+    doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+) -> Option<Vec<Location>> {
+    // 1. Find the namespace declaration, either directly or through a resolved reference
+    let occurrence = model.occurrence_at(lsp_position_to_ts_point(pos))?;
+    if model.symbol(occurrence.symbol).kind != SymbolKind::Namespace {
+        return None;
+    }
 
-    // 2. Resolve the namespace declaration
-    let declaration = match DeclaredNamespace::from_node(node) {
-        Some(decl) => decl,
-        None => match ReferencedNamespace::from_node(node) {
-            Some(ns) => ns.resolve_declaration(doc)?,
-            None => return None,
-        },
-    };
-
-    // 3. We have a definite declaration, so we can resolve references
+    // 2. We have a definite declaration, so we can gather its references
     let url = doc.url().clone();
-    let locations = declaration
-        .resolve_references(doc)
-        .into_iter()
+    let locations = model
+        .references_to(occurrence.symbol)
         .map(|ns| Location {
             uri: url.clone(),
-            range: ts_range_to_lsp_range(ns.identifier_range()),
+            range: ts_range_to_lsp_range(ns.identifier),
         })
         .collect();
 
@@ -0,0 +1,278 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use async_language_server::{
+    lsp_types::{Location, Position, Range, Url},
+    server::Document,
+    tree_sitter::Node,
+};
+
+use crate::{
+    utils::{api_path, apply_casing, find_option_path, find_option_value},
+    workspace::Workspace,
+};
+
+const USAGE_EXTENSIONS: [&str; 3] = ["luau", "lua", "ts"];
+
+/**
+    Members of the generated API for events and functions, in `PascalCase`.
+*/
+const API_MEMBERS: [&str; 9] = [
+    "Fire",
+    "FireAll",
+    "FireExcept",
+    "FireList",
+    "FireSet",
+    "On",
+    "SetCallback",
+    "Iter",
+    "Call",
+];
+
+/**
+    Describes how a single event or function declaration is accessed through
+    the generated API, used to find its usages in Luau and TypeScript files.
+
+    ### Example
+
+    ```zap
+    opt casing = "camelCase"
+
+    namespace Inventory = {
+        event ItemAdded = { ... }
+    }
+    ```
+
+    Would be accessed as `Inventory.ItemAdded.fire(...)`.
+*/
+#[derive(Debug, Clone)]
+pub struct GeneratedApiAccess {
+    /// `Inventory.ItemAdded`
+    path: String,
+    /// `fire`, `fireAll`, `on`, ...
+    members: Vec<String>,
+    /// `server`, `client`
+    modules: Vec<String>,
+    /// The generated output files themselves, which are never searched
+    outputs: Vec<PathBuf>,
+}
+
+impl GeneratedApiAccess {
+    /**
+        Constructs a new `GeneratedApiAccess` from the name node
+        of an event or function declaration, if the node is one.
+    */
+    pub fn from_node(doc: &Document, node: Node) -> Option<Self> {
+        let decl = node.parent()?;
+        if !matches!(decl.kind(), "event_declaration" | "function_declaration")
+            || decl.child_by_field_name("name") != Some(node)
+        {
+            return None;
+        }
+
+        let casing = find_option_value(doc, "casing");
+        let members = API_MEMBERS
+            .iter()
+            .map(|member| apply_casing(member, casing.as_deref()))
+            .collect();
+
+        let outputs = ["server_output", "client_output"]
+            .into_iter()
+            .filter_map(|option| find_option_path(doc, option))
+            .collect::<Vec<_>>();
+        let modules = outputs
+            .iter()
+            .filter_map(|path| path.file_stem()?.to_str())
+            .map(str::to_lowercase)
+            .collect();
+
+        Some(Self {
+            path: api_path(doc, decl),
+            members,
+            modules,
+            outputs,
+        })
+    }
+
+    /**
+        Returns `true` if the scanned file imports any of the generated output modules.
+
+        If no outputs are configured, any file could be importing the generated API.
+    */
+    fn is_imported_by(&self, scan: &FileScan) -> bool {
+        self.modules.is_empty()
+            || scan
+                .imports
+                .iter()
+                .any(|line| self.modules.iter().any(|module| line.contains(module)))
+    }
+
+    /**
+        Finds all accesses to the generated API in the scanned file.
+
+        Returned ranges cover the event or function name in each access.
+    */
+    fn find_in(&self, scan: &FileScan) -> Vec<Range> {
+        scan.accesses
+            .get(&self.path)
+            .into_iter()
+            .flatten()
+            .filter(|(member, _)| self.members.contains(member))
+            .map(|(_, range)| *range)
+            .collect()
+    }
+}
+
+/**
+    All member accesses in a single Luau or TypeScript file, such as
+    `Inventory.ItemAdded.fire`, indexed by the path that is accessed.
+
+    Scanning a file once lets every event and function look up its own
+    usages, instead of searching through the full file contents each time.
+*/
+#[derive(Debug, Default)]
+pub struct FileScan {
+    /// Lowercased lines that import or require any other module
+    imports: Vec<String>,
+    /// `Inventory.ItemAdded` to each accessed member, and the range of `ItemAdded`
+    accesses: HashMap<String, Vec<(String, Range)>>,
+}
+
+impl FileScan {
+    /**
+        Scans the given file source for imports and member accesses.
+    */
+    pub fn new(source: &str) -> Self {
+        let mut scan = Self::default();
+        for (line_index, line) in source.lines().enumerate() {
+            let lowercase = line.to_lowercase();
+            if lowercase.contains("require") || lowercase.contains("import") {
+                scan.imports.push(lowercase);
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            scan.add_line(line_index as u32, line);
+        }
+        scan
+    }
+
+    fn add_line(&mut self, line_index: u32, line: &str) {
+        let words = identifier_spans(line);
+
+        // Each access is a path of words separated by `.`, followed by a member
+        // after a `.` or `:`, and any suffix of a path may be the generated API
+        let mut path_start = 0;
+        for (index, &(start, end)) in words.iter().enumerate().skip(1) {
+            let (_, prev_end) = words[index - 1];
+            let separator = &line[prev_end..start];
+            if separator != "." && separator != ":" {
+                path_start = index;
+                continue;
+            }
+
+            let member = &line[start..end];
+            let (name_start, name_end) = words[index - 1];
+            let range = Range::new(
+                Position::new(line_index, utf16_len(&line[..name_start])),
+                Position::new(line_index, utf16_len(&line[..name_end])),
+            );
+            for &(first, _) in &words[path_start..index] {
+                self.accesses
+                    .entry(line[first..prev_end].to_string())
+                    .or_default()
+                    .push((member.to_string(), range));
+            }
+
+            // Members accessed using `:` are methods, which end the path
+            if separator == ":" {
+                path_start = index;
+            }
+        }
+    }
+}
+
+/**
+    Searches all Luau and TypeScript files in the given workspace,
+    which import the generated output, for accesses to the API.
+*/
+pub async fn usages(workspace: &Workspace, access: &GeneratedApiAccess) -> Vec<Location> {
+    let files = match workspace.files().await {
+        Ok(files) => files,
+        Err(e) => {
+            tracing::error!("Failed to walk workspace for usages: {e}");
+            return Vec::new();
+        }
+    };
+
+    let files = files.iter().filter(|path| {
+        has_usage_extension(path)
+            && !access
+                .outputs
+                .iter()
+                .any(|output| is_same_file(path, output))
+    });
+
+    let mut locations = Vec::new();
+    for path in files {
+        let Some(scan) = workspace.scan(path).await else {
+            continue;
+        };
+        if !access.is_imported_by(&scan) {
+            continue;
+        }
+        let Ok(uri) = Url::from_file_path(path) else {
+            continue;
+        };
+        locations.extend(access.find_in(&scan).into_iter().map(|range| Location {
+            uri: uri.clone(),
+            range,
+        }));
+    }
+
+    locations
+}
+
+fn has_usage_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| USAGE_EXTENSIONS.contains(&ext))
+}
+
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/**
+    Returns the byte ranges of all words made up of identifier characters in the line.
+*/
+fn identifier_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (index, c) in line.char_indices() {
+        match (is_identifier_char(c), start) {
+            (true, None) => start = Some(index),
+            (false, Some(s)) => {
+                spans.push((s, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, line.len()));
+    }
+    spans
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn utf16_len(s: &str) -> u32 {
+    s.encode_utf16().count() as u32
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use async_language_server::{
+    lsp_types::{Position, PrepareRenameResponse, TextEdit, WorkspaceEdit},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+};
+
+use crate::{
+    model::{SemanticModel, Symbol, SymbolId, SymbolKind},
+    utils::source,
+};
+
+use super::validation::{RenameError, validate_name, validate_siblings};
+
+pub fn prepare(
+    _doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+) -> Option<PrepareRenameResponse> {
+    let (_, symbol) = find_declaration(model, pos)?;
+    Some(PrepareRenameResponse::Range(ts_range_to_lsp_range(
+        symbol.identifier,
+    )))
+}
+
+pub fn rename(
+    doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+    new_name: &str,
+) -> Option<Result<WorkspaceEdit, RenameError>> {
+    // 1. Find the event or function declaration we are renaming
+    let (id, symbol) = find_declaration(model, pos)?;
+    let src = source(doc)?;
+    let declaration = model.declaration_node(&src, id)?;
+
+    // 2. Make sure the new name is valid and does not conflict with anything
+    if let Err(e) =
+        validate_name(new_name).and_then(|()| validate_siblings(doc, declaration, new_name))
+    {
+        return Some(Err(e));
+    }
+
+    // 3. Edit the declaration - events and functions can not be
+    //    referenced from within a Zap file, so this is the only edit
+    let edits = vec![TextEdit {
+        range: ts_range_to_lsp_range(symbol.identifier),
+        new_text: new_name.to_string(),
+    }];
+
+    // 4. Finally, build the full change set
+    let url = doc.url().clone();
+    let changes = HashMap::from([(url, edits)]);
+
+    Some(Ok(WorkspaceEdit {
+        changes: Some(changes),
+        document_changes: None,
+        change_annotations: None,
+    }))
+}
+
+/**
+    Finds the event or function declaration with its name at the given position.
+*/
+fn find_declaration(model: &SemanticModel, pos: Position) -> Option<(SymbolId, &Symbol)> {
+    let occurrence = model
+        .occurrence_at(lsp_position_to_ts_point(pos))
+        .filter(|o| o.is_declaration)?;
+    let symbol = model.symbol(occurrence.symbol);
+    if matches!(symbol.kind, SymbolKind::Event | SymbolKind::Function) {
+        Some((occurrence.symbol, symbol))
+    } else {
+        None
+    }
+}
@@ -1,8 +1,12 @@
+mod events;
 mod namespaces;
 mod types;
+mod validation;
 
+pub use self::events::rename as rename_for_events;
 pub use self::namespaces::rename as rename_for_namespaces;
 pub use self::types::rename as rename_for_types;
 
+pub use self::events::prepare as rename_prepare_for_events;
 pub use self::namespaces::prepare as rename_prepare_for_namespaces;
 pub use self::types::prepare as rename_prepare_for_types;
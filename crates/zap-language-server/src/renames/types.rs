@@ -4,78 +4,67 @@ use async_language_server::{
     lsp_types::{Position, PrepareRenameResponse, TextEdit, WorkspaceEdit},
     server::Document,
     tree_sitter::Node,
-    tree_sitter_utils::{ts_range_contains_lsp_position, ts_range_to_lsp_range},
+    tree_sitter_utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
 };
 
-use crate::structs::{DeclaredType, ReferencedType};
+use crate::{
+    model::{SemanticModel, SymbolKind},
+    utils::source,
+};
 
-pub fn prepare(_doc: &Document, pos: Position, node: Node) -> Option<PrepareRenameResponse> {
-    // 1. Check if we are renaming the identifier part of a declaration
-    if let Some(decl) = node.parent().and_then(DeclaredType::from_node) {
-        if ts_range_contains_lsp_position(decl.identifier_range(), pos) {
-            return Some(PrepareRenameResponse::Range(ts_range_to_lsp_range(
-                decl.identifier_range(),
-            )));
-        }
-    }
+use super::validation::{RenameError, validate_name, validate_siblings};
 
-    // 2. Check if we are renaming the identifier part of a reference
-    let node = if node.kind() == "namespaced_type" {
-        node.child_by_field_name("type")?
-    } else {
-        node
-    };
-    if let Some(typ) = ReferencedType::from_node(node) {
-        if ts_range_contains_lsp_position(typ.identifier_range(), pos) {
-            return Some(PrepareRenameResponse::Range(ts_range_to_lsp_range(
-                typ.identifier_range(),
-            )));
-        }
+pub fn prepare(
+    _doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+) -> Option<PrepareRenameResponse> {
+    // Check if we are renaming the identifier part of a declaration or
+    // reference, qualified references are only renamed at their final identifier
+    let occurrence = model.occurrence_at(lsp_position_to_ts_point(pos))?;
+    if model.symbol(occurrence.symbol).kind != SymbolKind::Type {
+        return None;
     }
 
-    None
+    Some(PrepareRenameResponse::Range(ts_range_to_lsp_range(
+        occurrence.identifier,
+    )))
 }
 
-pub fn rename(doc: &Document, pos: Position, node: Node, new_name: &str) -> Option<WorkspaceEdit> {
-    // 1. Transform the identifier node we are possibly on, into the
-    //    full node for the declaration / reference, when possible
-    let node = match node.parent() {
-        Some(p) if matches!(p.kind(), "type_declaration" | "namespaced_type") => p,
-        _ => node,
-    };
+pub fn rename(
+    doc: &Document,
+    model: &SemanticModel,
+    pos: Position,
+    _node: Node,
+    new_name: &str,
+) -> Option<Result<WorkspaceEdit, RenameError>> {
+    // 1. Find the type declaration, making sure that our position is over an identifier
+    let occurrence = model.occurrence_at(lsp_position_to_ts_point(pos))?;
+    let symbol = model.symbol(occurrence.symbol);
+    if symbol.kind != SymbolKind::Type {
+        return None;
+    }
 
-    // 2. Find the type declaration and all type references to it,
-    //    also making sure that our position is over an identifier
-    let declaration = match DeclaredType::from_node(node) {
-        Some(decl) => {
-            if ts_range_contains_lsp_position(decl.identifier_range(), pos) {
-                decl
-            } else {
-                return None;
-            }
-        }
-        None => match ReferencedType::from_node(node) {
-            Some(typ) => {
-                if ts_range_contains_lsp_position(typ.identifier_range(), pos) {
-                    typ.resolve_declaration(doc)?
-                } else {
-                    return None;
-                }
-            }
-            None => return None,
-        },
-    };
+    // 2. Make sure the new name is valid and does not conflict with anything
+    let src = source(doc)?;
+    let declaration = model.declaration_node(&src, occurrence.symbol)?;
+    if let Err(e) =
+        validate_name(new_name).and_then(|()| validate_siblings(doc, declaration, new_name))
+    {
+        return Some(Err(e));
+    }
 
     // 3. Edit the type declaration
     let mut edits = vec![TextEdit {
-        range: ts_range_to_lsp_range(declaration.identifier_range()),
+        range: ts_range_to_lsp_range(symbol.identifier),
         new_text: new_name.to_string(),
     }];
 
     // 4. Edit any references to the type
-    for type_reference in declaration.resolve_references(doc) {
+    for type_reference in model.references_to(occurrence.symbol) {
         edits.push(TextEdit {
-            range: ts_range_to_lsp_range(type_reference.identifier_range()),
+            range: ts_range_to_lsp_range(type_reference.identifier),
             new_text: new_name.to_string(),
         });
     }
@@ -84,9 +73,9 @@ pub fn rename(doc: &Document, pos: Position, node: Node, new_name: &str) -> Opti
     let url = doc.url().clone();
     let changes = HashMap::from([(url, edits)]);
 
-    Some(WorkspaceEdit {
+    Some(Ok(WorkspaceEdit {
         changes: Some(changes),
         document_changes: None,
         change_annotations: None,
-    })
+    }))
 }
@@ -0,0 +1,134 @@
+use async_language_server::{
+    server::{Document, ServerError},
+    tree_sitter::Node,
+};
+use zap_language::constants::{KEYWORD_DEFINITIONS, PRIMITIVE_DEFINITIONS};
+
+/**
+    An error that prevents a rename from being performed.
+*/
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RenameError {
+    #[error("name must not be empty")]
+    Empty,
+    #[error(
+        "`{0}` is not a valid identifier - identifiers must start with a letter or underscore, followed by letters, digits or underscores"
+    )]
+    InvalidIdentifier(String),
+    #[error("`{0}` is a primitive type and can not be used as a name")]
+    Primitive(String),
+    #[error("`{0}` is a keyword and can not be used as a name")]
+    Keyword(String),
+    #[error("a {kind} named `{name}` already exists in this namespace")]
+    Collision { kind: &'static str, name: String },
+    #[error("a {kind} named `{name}` already exists in this namespace and would be shadowed")]
+    Shadow { kind: &'static str, name: String },
+}
+
+impl From<RenameError> for ServerError {
+    /**
+        Invalid names are invalid request params, while names that are valid
+        but conflict with other declarations fail the request instead.
+    */
+    fn from(error: RenameError) -> Self {
+        if matches!(
+            error,
+            RenameError::Collision { .. } | RenameError::Shadow { .. }
+        ) {
+            ServerError::request_failed(error)
+        } else {
+            ServerError::invalid_params(error)
+        }
+    }
+}
+
+/**
+    Validates a new name against Zap identifier rules,
+    making sure it is not a primitive type or keyword.
+*/
+pub fn validate_name(new_name: &str) -> Result<(), RenameError> {
+    let mut chars = new_name.chars();
+    let Some(first) = chars.next() else {
+        return Err(RenameError::Empty);
+    };
+
+    let is_valid_start = first.is_ascii_alphabetic() || first == '_';
+    if !is_valid_start || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(RenameError::InvalidIdentifier(new_name.to_string()));
+    }
+
+    if PRIMITIVE_DEFINITIONS
+        .iter()
+        .any(|(name, _, _)| *name == new_name)
+    {
+        return Err(RenameError::Primitive(new_name.to_string()));
+    }
+
+    if KEYWORD_DEFINITIONS
+        .iter()
+        .any(|(name, _, _)| *name == new_name)
+    {
+        return Err(RenameError::Keyword(new_name.to_string()));
+    }
+
+    Ok(())
+}
+
+/**
+    Validates that renaming the given declaration would not collide with,
+    or shadow, any of the other declarations in the same namespace.
+
+    Declarations of the same kind collide, since references would then
+    resolve to only one of them, and types and namespaces with the same
+    name shadow each other in the generated output. Events, functions and
+    namespaces share a single set of names in the generated API, so those
+    always collide with each other.
+*/
+pub fn validate_siblings(doc: &Document, decl: Node, new_name: &str) -> Result<(), RenameError> {
+    let Some(namespace) = decl.parent() else {
+        return Ok(());
+    };
+
+    let mut cursor = namespace.walk();
+    for sibling in namespace.children(&mut cursor) {
+        if sibling == decl || !shares_names(sibling, decl) {
+            continue;
+        }
+        let Some(name) = sibling.child_by_field_name("name") else {
+            continue;
+        };
+        if doc.node_text(name) != new_name {
+            continue;
+        }
+
+        let kind = match sibling.kind() {
+            "type_declaration" => "type",
+            "namespace_declaration" => "namespace",
+            "event_declaration" => "event",
+            "function_declaration" => "function",
+            _ => continue,
+        };
+        let name = new_name.to_string();
+        if sibling.kind() == decl.kind() || is_api(sibling) || is_api(decl) {
+            return Err(RenameError::Collision { kind, name });
+        }
+        return Err(RenameError::Shadow { kind, name });
+    }
+
+    Ok(())
+}
+
+fn shares_names(a: Node, b: Node) -> bool {
+    // Types live only in the types output, and events and
+    // functions only in the generated API, while namespaces
+    // are part of both, so they share names with everything
+    is_api(a) == is_api(b) || is_namespace(a) || is_namespace(b)
+}
+
+fn is_namespace(node: Node) -> bool {
+    node.kind() == "namespace_declaration"
+}
+
+fn is_api(node: Node) -> bool {
+    matches!(node.kind(), "event_declaration" | "function_declaration")
+}
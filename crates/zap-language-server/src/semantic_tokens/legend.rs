@@ -0,0 +1,76 @@
+use async_language_server::lsp_types::{
+    SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend,
+};
+
+/**
+    The kind of a single semantic token, as classified by its resolved meaning.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Namespace,
+    Type,
+    Primitive,
+    EnumMember,
+    Property,
+    Option,
+    Event,
+    Function,
+    Unresolved,
+}
+
+impl TokenKind {
+    /**
+        Returns the index of this token kind in the token types of [`legend`].
+    */
+    pub const fn type_index(self) -> u32 {
+        match self {
+            Self::Namespace => 0,
+            Self::Type | Self::Primitive => 1,
+            Self::EnumMember => 2,
+            Self::Property => 3,
+            Self::Option => 4,
+            Self::Event => 5,
+            Self::Function => 6,
+            Self::Unresolved => 7,
+        }
+    }
+
+    /**
+        Returns the default modifier bitset for this token kind, using the token modifiers of [`legend`].
+    */
+    pub const fn modifier_bits(self) -> u32 {
+        match self {
+            Self::Primitive => MODIFIER_DEFAULT_LIBRARY,
+            Self::Option => MODIFIER_READONLY,
+            _ => 0,
+        }
+    }
+}
+
+pub const MODIFIER_DECLARATION: u32 = 1 << 0;
+pub const MODIFIER_DEFAULT_LIBRARY: u32 = 1 << 1;
+pub const MODIFIER_READONLY: u32 = 1 << 2;
+
+/**
+    Returns the semantic tokens legend, which must match the
+    indices in [`TokenKind::type_index`] and the modifier bits above.
+*/
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![
+            SemanticTokenType::NAMESPACE,
+            SemanticTokenType::TYPE,
+            SemanticTokenType::ENUM_MEMBER,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::VARIABLE,
+            SemanticTokenType::EVENT,
+            SemanticTokenType::FUNCTION,
+            SemanticTokenType::new("unresolvedReference"),
+        ],
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::DEFAULT_LIBRARY,
+            SemanticTokenModifier::READONLY,
+        ],
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use async_language_server::lsp_types::{
+    SemanticToken, SemanticTokens, SemanticTokensDelta, SemanticTokensEdit,
+    SemanticTokensFullDeltaResult, Url,
+};
+
+mod legend;
+mod tokens;
+
+pub use self::legend::legend as semantic_tokens_legend;
+pub use self::tokens::semantic_tokens;
+
+/**
+    A cache of the most recently sent semantic tokens for each document,
+    used to compute deltas for `textDocument/semanticTokens/full/delta`.
+
+    Cheap to clone, all clones share the same underlying cache.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct SemanticTokensCache {
+    next_id: Arc<AtomicU64>,
+    previous: Arc<Mutex<HashMap<Url, (String, Vec<SemanticToken>)>>>,
+}
+
+impl SemanticTokensCache {
+    /**
+        Stores the given tokens as the most recent tokens for the given
+        document, and returns the new, unique, result id for them.
+    */
+    pub fn store(&self, url: Url, tokens: Vec<SemanticToken>) -> String {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        if let Ok(mut previous) = self.previous.lock() {
+            previous.insert(url, (id.clone(), tokens));
+        }
+        id
+    }
+
+    /**
+        Computes the delta between the previously stored tokens and the given new tokens.
+
+        Falls back to sending the full set of tokens if the previous
+        result id is unknown or no longer the most recent one.
+    */
+    pub fn delta(
+        &self,
+        url: Url,
+        previous_result_id: &str,
+        tokens: Vec<SemanticToken>,
+    ) -> SemanticTokensFullDeltaResult {
+        let previous = self.previous.lock().ok().and_then(|p| {
+            p.get(&url)
+                .filter(|(id, _)| id == previous_result_id)
+                .map(|(_, old)| old.clone())
+        });
+
+        let Some(old) = previous else {
+            let id = self.store(url, tokens.clone());
+            return SemanticTokensFullDeltaResult::Tokens(SemanticTokens {
+                result_id: Some(id),
+                data: tokens,
+            });
+        };
+
+        // Find the single changed region in the middle of the two token lists,
+        // edit offsets and counts are in units of integers, 5 per token
+        let prefix = old.iter().zip(&tokens).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(tokens[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let edits = if prefix == old.len() && prefix == tokens.len() {
+            Vec::new()
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            vec![SemanticTokensEdit {
+                start: (prefix * 5) as u32,
+                delete_count: ((old.len() - prefix - suffix) * 5) as u32,
+                data: Some(tokens[prefix..tokens.len() - suffix].to_vec()),
+            }]
+        };
+
+        let id = self.store(url, tokens);
+        SemanticTokensFullDeltaResult::TokensDelta(SemanticTokensDelta {
+            result_id: Some(id),
+            edits,
+        })
+    }
+}
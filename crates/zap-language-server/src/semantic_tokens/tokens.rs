@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use async_language_server::{
+    lsp_types::{Range, SemanticToken},
+    server::Document,
+    tree_sitter::Node,
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_language::{
+    ast::{AstNode, EnumVariant, OptionDecl, PrimitiveType},
+    tree_sitter_utils::{DepthFirstNodeIterator, is_field_value_node},
+};
+
+use crate::{
+    model::{Reference, SemanticModel, SymbolKind},
+    utils::source,
+};
+
+use super::legend::{MODIFIER_DECLARATION, TokenKind};
+
+/**
+    Classifies and encodes all semantic tokens in the given document.
+
+    If a `range` is given, only tokens fully contained within that range are returned.
+*/
+pub fn semantic_tokens(
+    doc: &Document,
+    model: &SemanticModel,
+    range: Option<Range>,
+) -> Vec<SemanticToken> {
+    let Some(src) = source(doc) else {
+        return Vec::new();
+    };
+
+    // References are looked up for every identifier, so index them by where they start
+    let references = model
+        .references()
+        .map(|reference| (reference.identifier.start_byte, reference))
+        .collect::<HashMap<_, _>>();
+
+    let mut tokens = Vec::new();
+    let mut prev_line = 0;
+    let mut prev_start = 0;
+
+    for node in DepthFirstNodeIterator::new(src.root()) {
+        let Some((kind, modifiers)) = classify(&references, node) else {
+            continue;
+        };
+
+        // Tokens spanning multiple lines are not supported by all clients,
+        // and nothing we classify should ever span multiple lines anyway
+        let token_range = ts_range_to_lsp_range(node.range());
+        if token_range.start.line != token_range.end.line {
+            continue;
+        }
+        if range.is_some_and(|r| token_range.start < r.start || token_range.end > r.end) {
+            continue;
+        }
+
+        let line = token_range.start.line;
+        let start = token_range.start.character;
+
+        let delta_line = line - prev_line;
+        let delta_start = if delta_line == 0 {
+            start - prev_start
+        } else {
+            start
+        };
+
+        tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: token_range.end.character - start,
+            token_type: kind.type_index(),
+            token_modifiers_bitset: kind.modifier_bits() | modifiers,
+        });
+
+        prev_line = line;
+        prev_start = start;
+    }
+
+    tokens
+}
+
+fn classify(references: &HashMap<usize, &Reference>, node: Node) -> Option<(TokenKind, u32)> {
+    let parent = node.parent()?;
+
+    // Primitive types such as `u8` or `Instance`, but not their specifiers
+    if let Some(primitive) = PrimitiveType::cast(parent) {
+        return (primitive.name() == Some(node)).then_some((TokenKind::Primitive, 0));
+    }
+
+    // Enum-like field values such as `Server` or `Reliable`
+    if is_field_value_node(node) {
+        return Some((TokenKind::EnumMember, 0));
+    }
+
+    if node.kind() != "identifier" {
+        return None;
+    }
+
+    let is_name = parent.child_by_field_name("name") == Some(node);
+    match parent.kind() {
+        "namespace_declaration" if is_name => Some((TokenKind::Namespace, MODIFIER_DECLARATION)),
+        "type_declaration" if is_name => Some((TokenKind::Type, MODIFIER_DECLARATION)),
+        "event_declaration" if is_name => Some((TokenKind::Event, MODIFIER_DECLARATION)),
+        "function_declaration" if is_name => Some((TokenKind::Function, MODIFIER_DECLARATION)),
+        "property" | "tuple_value" if is_name => Some((TokenKind::Property, 0)),
+        "enum_variant" if EnumVariant::cast(parent).and_then(|v| v.name()) == Some(node) => {
+            Some((TokenKind::EnumMember, 0))
+        }
+        // Option names such as `casing`, and enum-like option values such as `ManySync`
+        "option_declaration" if OptionDecl::cast(parent).and_then(|o| o.name()) == Some(node) => {
+            Some((TokenKind::Option, 0))
+        }
+        "option_declaration" => Some((TokenKind::EnumMember, 0)),
+        _ => {
+            // Node is A or B, or C, in a namespaced type like A.B.C, or a plain reference
+            let reference = references.get(&node.start_byte())?;
+            if reference.target.is_none() {
+                Some((TokenKind::Unresolved, 0))
+            } else if reference.kind == SymbolKind::Namespace {
+                Some((TokenKind::Namespace, 0))
+            } else {
+                Some((TokenKind::Type, 0))
+            }
+        }
+    }
+}
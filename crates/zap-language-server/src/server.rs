@@ -1,41 +1,93 @@
 use async_language_server::{
     lsp_types::{
-        ClientCapabilities, CompletionItem, CompletionOptions, CompletionParams,
-        CompletionResponse, DiagnosticOptions, DiagnosticServerCapabilities,
+        ClientCapabilities, CodeActionKind, CodeActionOptions, CodeActionParams,
+        CodeActionProviderCapability, CodeActionResponse, CodeLens, CodeLensOptions,
+        CodeLensParams, CompletionOptions, CompletionParams, CompletionResponse, DiagnosticOptions,
+        DiagnosticServerCapabilities, DidChangeConfigurationParams, DidChangeWatchedFilesParams,
+        DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
         DocumentDiagnosticParams, DocumentDiagnosticReport, DocumentDiagnosticReportResult,
-        DocumentFormattingParams, FullDocumentDiagnosticReport, GotoDefinitionParams,
-        GotoDefinitionResponse, Hover, HoverParams, HoverProviderCapability, Location, OneOf,
+        DocumentFormattingParams, DocumentHighlight, DocumentHighlightParams, DocumentSymbolParams,
+        DocumentSymbolResponse, ExecuteCommandOptions, ExecuteCommandParams, FileChangeType,
+        FoldingRange, FoldingRangeParams, FoldingRangeProviderCapability,
+        FullDocumentDiagnosticReport, GotoDefinitionParams, GotoDefinitionResponse, Hover,
+        HoverParams, HoverProviderCapability, InitializeParams, Location, OneOf,
         PrepareRenameResponse, ReferenceParams, RelatedFullDocumentDiagnosticReport, RenameOptions,
-        RenameParams, ServerCapabilities, ServerInfo, TextDocumentPositionParams, TextEdit,
-        WorkDoneProgressOptions, WorkspaceEdit,
+        RenameParams, SelectionRange, SelectionRangeParams, SelectionRangeProviderCapability,
+        SemanticTokens, SemanticTokensDeltaParams, SemanticTokensFullDeltaResult,
+        SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensParams,
+        SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult,
+        SemanticTokensServerCapabilities, ServerCapabilities, ServerInfo,
+        TextDocumentPositionParams, TextEdit, WorkDoneProgressOptions, WorkspaceEdit,
+        WorkspaceSymbolParams, WorkspaceSymbolResponse,
     },
     server::{DocumentMatcher, Server, ServerError, ServerResult, ServerState},
     tree_sitter_utils::ts_range_to_lsp_range,
 };
+use serde_json::Value;
 use zap_formatter::Config;
 
 use crate::{
+    actions::{REFACTOR_MOVE, quick_fixes, refactors},
+    codegen::{
+        GENERATE_COMMAND, GENERATED_OUTPUT_COMMAND, GenerationFailures, StaleOutputs,
+        document_url_argument, generate_files, generated_output,
+    },
+    compiler::Compiler,
     completions::{
         completion_for_keywords, completion_for_namespaces, completion_for_options,
         completion_for_properties, completion_for_specifiers, completion_for_types, completion_pos,
         completion_trigger_characters,
     },
     definitions::{definition_for_namespaces, definition_for_types},
-    diagnostics::zap_diagnostic_to_lsp_diagnostic,
-    hovers::{hover_for_keywords, hover_for_options, hover_for_properties, hover_for_types},
-    references::{references_for_namespaces, references_for_types},
+    diagnostics::{unused_diagnostics, zap_diagnostic_to_lsp_diagnostic},
+    highlights::{highlight_for_events, highlight_for_namespaces, highlight_for_types},
+    hovers::{
+        hover_for_declarations, hover_for_keywords, hover_for_namespaces, hover_for_options,
+        hover_for_payloads, hover_for_properties, hover_for_types,
+    },
+    lenses::{lens_url_for_references, lenses_for_references, resolve_lens_for_references},
+    model::SemanticModelCache,
+    ranges::{folding_ranges, selection_ranges},
+    references::{
+        GeneratedApiAccess, references_for_events, references_for_namespaces, references_for_types,
+        usages_for_events,
+    },
     renames::{
-        rename_for_namespaces, rename_for_types, rename_prepare_for_namespaces,
-        rename_prepare_for_types,
+        rename_for_events, rename_for_namespaces, rename_for_types, rename_prepare_for_events,
+        rename_prepare_for_namespaces, rename_prepare_for_types,
     },
+    semantic_tokens::{SemanticTokensCache, semantic_tokens, semantic_tokens_legend},
+    settings::Settings,
+    symbols::{WorkspaceIndex, document_symbols},
+    workspace::Workspace,
 };
 
+const ZAP_DOCUMENT_GLOBS: [&str; 1] = ["*.zap"];
+
 #[derive(Debug, Clone)]
-pub struct ZapLanguageServer {}
+pub struct ZapLanguageServer {
+    workspace: Workspace,
+    symbols: WorkspaceIndex,
+    tokens: SemanticTokensCache,
+    generation: GenerationFailures,
+    stale: StaleOutputs,
+    models: SemanticModelCache,
+    compiler: Compiler,
+    settings: Settings,
+}
 
 impl ZapLanguageServer {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            workspace: Workspace::default(),
+            symbols: WorkspaceIndex::new(ZAP_DOCUMENT_GLOBS),
+            tokens: SemanticTokensCache::default(),
+            generation: GenerationFailures::default(),
+            stale: StaleOutputs::default(),
+            models: SemanticModelCache::default(),
+            compiler: Compiler::default(),
+            settings: Settings::default(),
+        }
     }
 }
 
@@ -64,12 +116,44 @@ impl Server for ZapLanguageServer {
             })),
             definition_provider: Some(OneOf::Left(true)),
             references_provider: Some(OneOf::Left(true)),
+            document_highlight_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![
+                    CodeActionKind::QUICKFIX,
+                    CodeActionKind::REFACTOR_EXTRACT,
+                    CodeActionKind::REFACTOR_INLINE,
+                    REFACTOR_MOVE,
+                ]),
+                ..Default::default()
+            })),
+            code_lens_provider: Some(CodeLensOptions {
+                resolve_provider: Some(true),
+            }),
             completion_provider: Some(CompletionOptions {
                 resolve_provider: Some(true),
                 trigger_characters: Some(completion_trigger_characters()),
                 ..Default::default()
             }),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![
+                    GENERATED_OUTPUT_COMMAND.to_string(),
+                    GENERATE_COMMAND.to_string(),
+                ],
+                ..Default::default()
+            }),
             document_formatting_provider: Some(OneOf::Left(true)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+            semantic_tokens_provider: Some(
+                SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: semantic_tokens_legend(),
+                    range: Some(true),
+                    full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                    ..Default::default()
+                }),
+            ),
             diagnostic_provider: Some(DiagnosticServerCapabilities::Options(DiagnosticOptions {
                 inter_file_dependencies: false,
                 workspace_diagnostics: false,
@@ -82,12 +166,20 @@ impl Server for ZapLanguageServer {
     fn server_document_matchers() -> Vec<DocumentMatcher> {
         vec![
             DocumentMatcher::new("Zap Document")
-                .with_url_globs(["*.zap"])
+                .with_url_globs(ZAP_DOCUMENT_GLOBS)
                 .with_lang_strings(["Zap"])
                 .with_lang_grammar(zap_language::TS_LANGUAGE.into()),
         ]
     }
 
+    async fn initialize(&self, _state: ServerState, params: InitializeParams) -> ServerResult<()> {
+        if let Some(options) = &params.initialization_options {
+            self.settings.update(options);
+        }
+        self.workspace.initialize(&params).await;
+        Ok(())
+    }
+
     async fn hover(&self, state: ServerState, params: HoverParams) -> ServerResult<Option<Hover>> {
         let url = params.text_document_position_params.text_document.uri;
         let pos = params.text_document_position_params.position;
@@ -102,10 +194,23 @@ impl Server for ZapLanguageServer {
 
         tracing::debug!("Getting hover for node at {}:{}", pos.line, pos.character);
 
-        Ok(hover_for_keywords(&doc, pos, node)
-            .or_else(|| hover_for_types(&doc, pos, node))
-            .or_else(|| hover_for_properties(&doc, pos, node))
-            .or_else(|| hover_for_options(&doc, pos, node)))
+        let model = self.models.get(&doc);
+        let hover = hover_for_keywords(&doc, pos, node)
+            .or_else(|| hover_for_declarations(&doc, &model, pos, node))
+            .or_else(|| hover_for_namespaces(&doc, &model, pos, node));
+        if hover.is_some() {
+            return Ok(hover);
+        }
+
+        // Type and payload hovers preview the generated types, which runs the compiler
+        if let Some(hover) = hover_for_types(&doc, &model, &self.compiler, pos, node).await {
+            return Ok(Some(hover));
+        }
+        if let Some(hover) = hover_for_payloads(&doc, &self.compiler, pos, node).await {
+            return Ok(Some(hover));
+        }
+
+        Ok(hover_for_properties(&doc, pos, node).or_else(|| hover_for_options(&doc, pos, node)))
     }
 
     async fn completion(
@@ -164,16 +269,7 @@ impl Server for ZapLanguageServer {
         if items.is_empty() {
             Ok(None)
         } else {
-            Ok(Some(CompletionResponse::Array(
-                items
-                    .into_iter()
-                    .map(|(kind, label)| CompletionItem {
-                        kind: Some(kind),
-                        label,
-                        ..Default::default()
-                    })
-                    .collect(),
-            )))
+            Ok(Some(CompletionResponse::Array(items)))
         }
     }
 
@@ -193,8 +289,10 @@ impl Server for ZapLanguageServer {
             return Ok(None);
         };
 
-        Ok(rename_prepare_for_namespaces(&doc, pos, node)
-            .or_else(|| rename_prepare_for_types(&doc, pos, node)))
+        let model = self.models.get(&doc);
+        Ok(rename_prepare_for_namespaces(&doc, &model, pos, node)
+            .or_else(|| rename_prepare_for_types(&doc, &model, pos, node))
+            .or_else(|| rename_prepare_for_events(&doc, &model, pos, node)))
     }
 
     async fn rename(
@@ -213,10 +311,12 @@ impl Server for ZapLanguageServer {
             return Ok(None);
         };
 
-        Ok(
-            rename_for_namespaces(&doc, pos, node, params.new_name.as_str())
-                .or_else(|| rename_for_types(&doc, pos, node, params.new_name.as_str())),
-        )
+        let model = self.models.get(&doc);
+        rename_for_namespaces(&doc, &model, pos, node, params.new_name.as_str())
+            .or_else(|| rename_for_types(&doc, &model, pos, node, params.new_name.as_str()))
+            .or_else(|| rename_for_events(&doc, &model, pos, node, params.new_name.as_str()))
+            .transpose()
+            .map_err(ServerError::from)
     }
 
     async fn definition(
@@ -249,8 +349,9 @@ impl Server for ZapLanguageServer {
             pos.character
         );
 
-        Ok(definition_for_namespaces(&doc, pos, node)
-            .or_else(|| definition_for_types(&doc, pos, node)))
+        let model = self.models.get(&doc);
+        Ok(definition_for_namespaces(&doc, &model, pos, node)
+            .or_else(|| definition_for_types(&doc, &model, pos, node)))
     }
 
     async fn references(
@@ -279,8 +380,100 @@ impl Server for ZapLanguageServer {
             pos.character
         );
 
-        Ok(references_for_namespaces(&doc, pos, node)
-            .or_else(|| references_for_types(&doc, pos, node)))
+        let model = self.models.get(&doc);
+        let mut locations = references_for_namespaces(&doc, &model, pos, node)
+            .or_else(|| references_for_types(&doc, &model, pos, node))
+            .or_else(|| references_for_events(&doc, &model, pos, node));
+
+        // Events and functions are used through the generated API in
+        // Luau and TypeScript files, so we also search the workspace for those
+        if let Some(access) = GeneratedApiAccess::from_node(&doc, node) {
+            let usages = usages_for_events(&self.workspace, &access).await;
+            locations.get_or_insert_with(Vec::new).extend(usages);
+        }
+
+        Ok(locations)
+    }
+
+    async fn document_highlight(
+        &self,
+        state: ServerState,
+        params: DocumentHighlightParams,
+    ) -> ServerResult<Option<Vec<DocumentHighlight>>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let pos = params.text_document_position_params.position;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+        let Some(node) = doc.node_at_position_named(pos) else {
+            tracing::debug!(
+                "Missing node for highlight at {}:{}",
+                pos.line,
+                pos.character
+            );
+            return Ok(None);
+        };
+
+        tracing::debug!(
+            "Getting highlights for node at {}:{}",
+            pos.line,
+            pos.character
+        );
+
+        let model = self.models.get(&doc);
+        Ok(highlight_for_namespaces(&doc, &model, pos, node)
+            .or_else(|| highlight_for_types(&doc, &model, pos, node))
+            .or_else(|| highlight_for_events(&doc, pos, node)))
+    }
+
+    async fn code_action(
+        &self,
+        state: ServerState,
+        params: CodeActionParams,
+    ) -> ServerResult<Option<CodeActionResponse>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        let mut actions = Vec::new();
+        actions.extend(quick_fixes(&doc, &params.context.diagnostics));
+        actions.extend(refactors(&doc, params.range));
+
+        if actions.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(actions))
+        }
+    }
+
+    async fn code_lens(
+        &self,
+        state: ServerState,
+        params: CodeLensParams,
+    ) -> ServerResult<Option<Vec<CodeLens>>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        Ok(Some(lenses_for_references(&doc)))
+    }
+
+    async fn code_lens_resolve(
+        &self,
+        state: ServerState,
+        lens: CodeLens,
+    ) -> ServerResult<CodeLens> {
+        let Some(doc) = lens_url_for_references(&lens).and_then(|url| state.document(&url)) else {
+            return Ok(lens);
+        };
+
+        let model = self.models.get(&doc);
+        Ok(resolve_lens_for_references(&doc, &model, &self.workspace, lens).await)
     }
 
     async fn document_format(
@@ -309,6 +502,231 @@ impl Server for ZapLanguageServer {
         Ok(Some(vec![TextEdit { range, new_text }]))
     }
 
+    async fn document_symbols(
+        &self,
+        state: ServerState,
+        params: DocumentSymbolParams,
+    ) -> ServerResult<Option<DocumentSymbolResponse>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        tracing::debug!("Getting document symbols for {url}");
+
+        Ok(Some(DocumentSymbolResponse::Nested(document_symbols(&doc))))
+    }
+
+    async fn folding_range(
+        &self,
+        state: ServerState,
+        params: FoldingRangeParams,
+    ) -> ServerResult<Option<Vec<FoldingRange>>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        Ok(Some(folding_ranges(&doc)))
+    }
+
+    async fn selection_range(
+        &self,
+        state: ServerState,
+        params: SelectionRangeParams,
+    ) -> ServerResult<Option<Vec<SelectionRange>>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        Ok(Some(selection_ranges(&doc, params.positions)))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        state: ServerState,
+        params: SemanticTokensParams,
+    ) -> ServerResult<Option<SemanticTokensResult>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        let model = self.models.get(&doc);
+        let data = semantic_tokens(&doc, &model, None);
+        let result_id = self.tokens.store(url, data.clone());
+
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: Some(result_id),
+            data,
+        })))
+    }
+
+    async fn semantic_tokens_full_delta(
+        &self,
+        state: ServerState,
+        params: SemanticTokensDeltaParams,
+    ) -> ServerResult<Option<SemanticTokensFullDeltaResult>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        let model = self.models.get(&doc);
+        let data = semantic_tokens(&doc, &model, None);
+        Ok(Some(self.tokens.delta(
+            url,
+            &params.previous_result_id,
+            data,
+        )))
+    }
+
+    async fn semantic_tokens_range(
+        &self,
+        state: ServerState,
+        params: SemanticTokensRangeParams,
+    ) -> ServerResult<Option<SemanticTokensRangeResult>> {
+        let url = params.text_document.uri;
+
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        let model = self.models.get(&doc);
+        let data = semantic_tokens(&doc, &model, Some(params.range));
+        Ok(Some(SemanticTokensRangeResult::Tokens(SemanticTokens {
+            result_id: None,
+            data,
+        })))
+    }
+
+    async fn workspace_symbols(
+        &self,
+        _state: ServerState,
+        params: WorkspaceSymbolParams,
+    ) -> ServerResult<Option<WorkspaceSymbolResponse>> {
+        /*
+            NOTE: Indexing is done lazily, on the first request, since it
+            may be slow for large workspaces and most sessions never need it.
+        */
+        self.symbols.index_workspace(&self.workspace).await;
+
+        tracing::debug!("Getting workspace symbols for query '{}'", params.query);
+
+        let symbols = self.symbols.search(&params.query);
+        Ok(Some(WorkspaceSymbolResponse::Nested(symbols)))
+    }
+
+    async fn did_change_watched_files(
+        &self,
+        _state: ServerState,
+        params: DidChangeWatchedFilesParams,
+    ) -> ServerResult<()> {
+        for change in params.changes {
+            let Ok(path) = change.uri.to_file_path() else {
+                continue;
+            };
+            if matches!(
+                change.typ,
+                FileChangeType::CREATED | FileChangeType::DELETED
+            ) {
+                self.workspace.invalidate_files().await;
+            }
+            if !self.symbols.matches(&path) {
+                continue;
+            }
+            if change.typ == FileChangeType::DELETED {
+                self.symbols.remove(&change.uri);
+            } else {
+                self.symbols.index_file(&path).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn did_change_configuration(
+        &self,
+        _state: ServerState,
+        params: DidChangeConfigurationParams,
+    ) -> ServerResult<()> {
+        self.settings.update(&params.settings);
+        Ok(())
+    }
+
+    async fn did_open(
+        &self,
+        state: ServerState,
+        params: DidOpenTextDocumentParams,
+    ) -> ServerResult<()> {
+        if let Some(doc) = state.document(&params.text_document.uri) {
+            self.stale.refresh(&doc, &self.compiler).await;
+        }
+        Ok(())
+    }
+
+    async fn did_save(
+        &self,
+        state: ServerState,
+        params: DidSaveTextDocumentParams,
+    ) -> ServerResult<()> {
+        let Some(doc) = state.document(&params.text_document.uri) else {
+            return Ok(());
+        };
+
+        if self.settings.generate_on_save() {
+            tracing::debug!("Generating output files on save for {}", doc.url());
+
+            // Any failures are stored and then shown as diagnostics for the document,
+            // and the outputs that were written are no longer stale, so no check is needed
+            generate_files(&doc, &self.compiler, &self.generation).await;
+            self.stale.remove(doc.url());
+        } else {
+            self.stale.refresh(&doc, &self.compiler).await;
+        }
+
+        Ok(())
+    }
+
+    async fn did_close(
+        &self,
+        _state: ServerState,
+        params: DidCloseTextDocumentParams,
+    ) -> ServerResult<()> {
+        self.stale.remove(&params.text_document.uri);
+        self.models.remove(&params.text_document.uri);
+        Ok(())
+    }
+
+    async fn execute_command(
+        &self,
+        state: ServerState,
+        params: ExecuteCommandParams,
+    ) -> ServerResult<Option<Value>> {
+        let Some(url) = document_url_argument(&params.arguments) else {
+            tracing::debug!("Missing document argument for command '{}'", params.command);
+            return Ok(None);
+        };
+        let Some(doc) = state.document(&url) else {
+            return Ok(None);
+        };
+
+        match params.command.as_str() {
+            GENERATED_OUTPUT_COMMAND => Ok(Some(generated_output(&doc, &self.compiler).await)),
+            GENERATE_COMMAND => {
+                let result = generate_files(&doc, &self.compiler, &self.generation).await;
+                self.stale.remove(doc.url());
+                Ok(Some(result))
+            }
+            _ => Ok(None),
+        }
+    }
+
     async fn document_diagnostics(
         &self,
         state: ServerState,
@@ -316,11 +734,17 @@ impl Server for ZapLanguageServer {
     ) -> ServerResult<DocumentDiagnosticReportResult> {
         let items = match state.document(&params.text_document.uri) {
             Some(doc) => {
-                let contents = doc.text_contents();
-                let parsed = zap_language::diagnostics::parse(&contents);
+                let model = self.models.get(&doc);
+                let parsed = self
+                    .compiler
+                    .diagnostics(doc.url(), doc.text_contents())
+                    .await;
                 parsed
                     .into_iter()
                     .filter_map(|diag| zap_diagnostic_to_lsp_diagnostic(&doc, diag))
+                    .chain(unused_diagnostics(&doc, &model))
+                    .chain(self.generation.get(doc.url()))
+                    .chain(self.stale.get(doc.url()))
                     .collect::<Vec<_>>()
             }
             None => Vec::new(),
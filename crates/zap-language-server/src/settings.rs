@@ -0,0 +1,44 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use serde_json::Value;
+
+/**
+    Name of the configuration section that the client sends settings for.
+*/
+const SETTINGS_SECTION: &str = "zap";
+
+/**
+    User settings sent by the client, both as initialization
+    options, and whenever the user changes their configuration.
+
+    Cheap to clone, all clones share the same underlying settings.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    generate_on_save: Arc<AtomicBool>,
+}
+
+impl Settings {
+    /**
+        Updates settings from the given value, which may either contain the
+        settings directly, or nested inside of a `zap` configuration section.
+
+        Settings that are missing from the value are left unchanged.
+    */
+    pub fn update(&self, value: &Value) {
+        let settings = value.get(SETTINGS_SECTION).unwrap_or(value);
+        if let Some(enabled) = settings.get("generateOnSave").and_then(Value::as_bool) {
+            self.generate_on_save.store(enabled, Ordering::Relaxed);
+        }
+    }
+
+    /**
+        Returns `true` if output files should be generated whenever a Zap file is saved.
+    */
+    pub fn generate_on_save(&self) -> bool {
+        self.generate_on_save.load(Ordering::Relaxed)
+    }
+}
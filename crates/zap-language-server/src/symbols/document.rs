@@ -0,0 +1,253 @@
+use async_language_server::{
+    lsp_types::{DocumentSymbol, SymbolKind},
+    server::Document,
+    tree_sitter::{Node, Range},
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use zap_analysis::{DeclaredNamespace, DeclaredType, Source};
+use zap_language::{
+    ast::{AstNode, EventDecl, FunctDecl, OptionDecl},
+    tree_sitter_utils::is_field_value_node,
+};
+
+use crate::utils::{doc_comment, source};
+
+/**
+    Gathers a nested tree of document symbols, starting at the root of the given document.
+
+    Namespaces contain all of their inner declarations as children, and types,
+    events and functions contain any struct fields and enum variants as children.
+*/
+pub fn symbols(doc: &Document) -> Vec<DocumentSymbol> {
+    source(doc)
+        .map(|src| symbols_in_namespace(&src, src.root()))
+        .unwrap_or_default()
+}
+
+fn symbols_in_namespace(src: &Source, namespace: Node) -> Vec<DocumentSymbol> {
+    let mut symbols = Vec::new();
+
+    let mut cursor = namespace.walk();
+    for child in namespace.children(&mut cursor) {
+        let symbol = match child.kind() {
+            "namespace_declaration" => {
+                DeclaredNamespace::from_node(child).map(|decl| symbol_for_namespace(src, &decl))
+            }
+            "type_declaration" => {
+                DeclaredType::from_node(child).map(|decl| symbol_for_type(src, &decl))
+            }
+            "event_declaration" | "function_declaration" => symbol_for_declaration(src, child),
+            "option_declaration" => symbol_for_option(src, child),
+            _ => None,
+        };
+        symbols.extend(symbol);
+    }
+
+    symbols
+}
+
+fn symbol_for_namespace(src: &Source, decl: &DeclaredNamespace) -> DocumentSymbol {
+    let children = symbols_in_namespace(src, *decl.as_ref());
+    new_symbol(
+        decl.identifier_text(src),
+        decl.doc_comment(src),
+        SymbolKind::NAMESPACE,
+        *decl.as_ref(),
+        decl.identifier_range(),
+        children,
+    )
+}
+
+fn symbol_for_type(src: &Source, decl: &DeclaredType) -> DocumentSymbol {
+    let value = decl.as_ref().child_by_field_name("value").map(unwrap_type);
+
+    let kind = symbol_kind_for_type(value);
+
+    // Doc comments take priority, otherwise structs and enums already show their
+    // contents as children, and anything else is short enough to show inline
+    let detail = decl.doc_comment(src).or_else(|| {
+        value
+            .filter(|v| !matches!(v.kind(), "struct_type" | "enum_type"))
+            .map(|v| src.node_text(v))
+    });
+
+    let children = value.map(|v| symbols_in_type(src, v)).unwrap_or_default();
+
+    new_symbol(
+        decl.identifier_text(src),
+        detail,
+        kind,
+        *decl.as_ref(),
+        decl.identifier_range(),
+        children,
+    )
+}
+
+fn symbol_for_declaration(src: &Source, node: Node) -> Option<DocumentSymbol> {
+    let (kind, name, fields) = if let Some(event) = EventDecl::cast(node) {
+        (SymbolKind::EVENT, event.name(), event.fields())
+    } else {
+        let funct = FunctDecl::cast(node)?;
+        (SymbolKind::FUNCTION, funct.name(), funct.fields())
+    };
+    let name = name?;
+
+    // The detail for events and functions is its doc comment, or a short
+    // summary of its enum-like fields, such as "Server, Reliable, ManyAsync"
+    let mut variants = Vec::new();
+    let mut children = Vec::new();
+
+    for field in fields {
+        let Some(value) = field.value() else {
+            continue;
+        };
+        if is_field_value_node(value) {
+            variants.push(src.node_text(value));
+        } else if matches!(field.key(), "data" | "args" | "rets") {
+            children.extend(symbols_in_type(src, unwrap_type(value)));
+        }
+    }
+
+    let detail =
+        doc_comment(src, node).or_else(|| (!variants.is_empty()).then(|| variants.join(", ")));
+
+    Some(new_symbol(
+        src.node_text(name),
+        detail,
+        kind,
+        node,
+        name.range(),
+        children,
+    ))
+}
+
+fn symbol_for_option(src: &Source, node: Node) -> Option<DocumentSymbol> {
+    let option = OptionDecl::cast(node)?;
+    let name = option.name()?;
+    let value = option.value().map(|n| src.node_text(n));
+
+    Some(new_symbol(
+        src.node_text(name),
+        value,
+        SymbolKind::CONSTANT,
+        node,
+        name.range(),
+        Vec::new(),
+    ))
+}
+
+fn symbols_in_type(src: &Source, node: Node) -> Vec<DocumentSymbol> {
+    let mut symbols = Vec::new();
+
+    let mut cursor = node.walk();
+    match node.kind() {
+        "struct_type" => {
+            for child in node.children(&mut cursor) {
+                symbols.extend(symbol_for_property(src, child));
+            }
+        }
+        "enum_type" => {
+            for child in node.children(&mut cursor) {
+                if child.kind() != "enum_variant" {
+                    continue;
+                }
+                let Some(ident) = child.child(0) else {
+                    continue;
+                };
+
+                // Tagged enum variants may contain struct-like fields
+                let mut child_cursor = child.walk();
+                let children = child
+                    .children(&mut child_cursor)
+                    .filter_map(|c| symbol_for_property(src, c))
+                    .collect();
+
+                symbols.push(new_symbol(
+                    src.node_text(ident),
+                    None,
+                    SymbolKind::ENUM_MEMBER,
+                    child,
+                    ident.range(),
+                    children,
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    symbols
+}
+
+fn symbol_for_property(src: &Source, node: Node) -> Option<DocumentSymbol> {
+    if node.kind() != "property" {
+        return None;
+    }
+
+    let name = node.child_by_field_name("name")?;
+    let typ = node.child_by_field_name("type").map(unwrap_type);
+
+    // Nested structs and enums get their own children instead of a detail
+    let (detail, children) = match typ {
+        Some(t) if matches!(t.kind(), "struct_type" | "enum_type") => {
+            (None, symbols_in_type(src, t))
+        }
+        Some(t) => (Some(src.node_text(t)), Vec::new()),
+        None => (None, Vec::new()),
+    };
+
+    Some(new_symbol(
+        src.node_text(name),
+        detail,
+        SymbolKind::FIELD,
+        node,
+        name.range(),
+        children,
+    ))
+}
+
+/**
+    Returns the symbol kind for the given type declaration value.
+*/
+pub(super) fn symbol_kind_for_type(value: Option<Node>) -> SymbolKind {
+    match value.map(|v| unwrap_type(v).kind()) {
+        Some("struct_type") => SymbolKind::STRUCT,
+        Some("enum_type") => SymbolKind::ENUM,
+        _ => SymbolKind::TYPE_PARAMETER,
+    }
+}
+
+/**
+    Unwraps the encompassing "type" node, if any, into its inner type node.
+*/
+fn unwrap_type(node: Node) -> Node {
+    if node.kind() == "type" {
+        node.child(0).unwrap_or(node)
+    } else {
+        node
+    }
+}
+
+#[allow(deprecated)] // DocumentSymbol::deprecated must be specified, even if unused
+fn new_symbol(
+    name: String,
+    detail: Option<String>,
+    kind: SymbolKind,
+    node: Node,
+    selection_range: Range,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name,
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: ts_range_to_lsp_range(node.range()),
+        selection_range: ts_range_to_lsp_range(selection_range),
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    }
+}
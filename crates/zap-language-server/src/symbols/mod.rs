@@ -0,0 +1,5 @@
+mod document;
+mod workspace;
+
+pub use self::document::symbols as document_symbols;
+pub use self::workspace::WorkspaceIndex;
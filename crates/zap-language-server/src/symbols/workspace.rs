@@ -0,0 +1,287 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use async_language_server::{
+    lsp_types::{Location, OneOf, Range, SymbolKind, Url, WorkspaceSymbol},
+    tree_sitter::{Node, Parser},
+    tree_sitter_utils::ts_range_to_lsp_range,
+};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use tokio::sync::Mutex;
+
+use crate::workspace::Workspace;
+
+use super::document::symbol_kind_for_type;
+
+const MAX_RESULTS: usize = 256;
+
+/**
+    A single declaration found while indexing the workspace.
+*/
+#[derive(Debug, Clone)]
+struct IndexedSymbol {
+    /// Fully qualified name, such as `Inventory.Item`
+    name: String,
+    kind: SymbolKind,
+    range: Range,
+}
+
+/**
+    An index of all declarations in all Zap files in the workspace,
+    including files that are not currently opened in the editor.
+
+    Cheap to clone, all clones share the same underlying index.
+*/
+#[derive(Debug, Clone)]
+pub struct WorkspaceIndex {
+    globs: Arc<GlobSet>,
+    indexed: Arc<Mutex<bool>>,
+    files: Arc<RwLock<HashMap<Url, Vec<IndexedSymbol>>>>,
+}
+
+impl WorkspaceIndex {
+    /**
+        Creates a new, empty, workspace index.
+
+        Only files matching any of the given globs will be indexed.
+    */
+    pub fn new<I, S>(globs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut builder = GlobSetBuilder::new();
+        for glob in globs {
+            match Glob::new(glob.as_ref()) {
+                Ok(g) => {
+                    builder.add(g);
+                }
+                Err(e) => tracing::error!("Invalid workspace glob: {e}"),
+            }
+        }
+
+        Self {
+            globs: Arc::new(builder.build().unwrap_or_else(|_| GlobSet::empty())),
+            indexed: Arc::new(Mutex::new(false)),
+            files: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /**
+        Returns `true` if the given path should be a part of the index.
+    */
+    pub fn matches(&self, path: &Path) -> bool {
+        self.globs.is_match(path)
+    }
+
+    /**
+        Indexes all matching files in the given workspace,
+        unless the workspace has already been fully indexed before.
+
+        Concurrent callers wait for indexing to finish, so that they never
+        see a partial index, and indexing is retried if walking the workspace fails.
+    */
+    pub async fn index_workspace(&self, workspace: &Workspace) {
+        let mut indexed = self.indexed.lock().await;
+        if *indexed {
+            return;
+        }
+
+        let files = match workspace.files().await {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::error!("Failed to walk workspace for indexing: {e}");
+                return;
+            }
+        };
+
+        for path in files.iter().filter(|path| self.matches(path)) {
+            self.index_file(path).await;
+        }
+
+        *indexed = true;
+
+        tracing::debug!(
+            "Indexed {} workspace files",
+            self.files.read().map(|f| f.len()).unwrap_or_default()
+        );
+    }
+
+    /**
+        Reads and indexes a single file from disk, replacing
+        any previously indexed declarations for that file.
+    */
+    pub async fn index_file(&self, path: &Path) {
+        let Ok(url) = Url::from_file_path(path) else {
+            return;
+        };
+        match tokio::fs::read_to_string(path).await {
+            Ok(source) => self.index_source(url, &source),
+            Err(_) => self.remove(&url),
+        }
+    }
+
+    /**
+        Indexes the given source contents, replacing any
+        previously indexed declarations for the given url.
+    */
+    pub fn index_source(&self, url: Url, source: &str) {
+        let mut parser = Parser::new();
+        if parser
+            .set_language(&zap_language::TS_LANGUAGE.into())
+            .is_err()
+        {
+            return;
+        }
+        let Some(tree) = parser.parse(source, None) else {
+            return;
+        };
+
+        let mut symbols = Vec::new();
+        collect_symbols(
+            source.as_bytes(),
+            tree.root_node(),
+            &mut Vec::new(),
+            &mut symbols,
+        );
+
+        if let Ok(mut files) = self.files.write() {
+            files.insert(url, symbols);
+        }
+    }
+
+    /**
+        Removes all indexed declarations for the given url.
+    */
+    pub fn remove(&self, url: &Url) {
+        if let Ok(mut files) = self.files.write() {
+            files.remove(url);
+        }
+    }
+
+    /**
+        Searches the index using the given fuzzy query, returning
+        the best matching symbols first, in descending score order.
+    */
+    pub fn search(&self, query: &str) -> Vec<WorkspaceSymbol> {
+        let Ok(files) = self.files.read() else {
+            return Vec::new();
+        };
+
+        let mut matches = Vec::new();
+        for (url, symbols) in files.iter() {
+            for symbol in symbols {
+                if let Some(score) = fuzzy_score(query, &symbol.name) {
+                    matches.push((score, url, symbol));
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.name.cmp(&b.2.name)));
+        matches
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|(_, url, symbol)| WorkspaceSymbol {
+                name: symbol.name.clone(),
+                kind: symbol.kind,
+                tags: None,
+                container_name: url
+                    .path_segments()
+                    .and_then(|mut s| s.next_back())
+                    .map(ToString::to_string),
+                location: OneOf::Left(Location {
+                    uri: url.clone(),
+                    range: symbol.range,
+                }),
+                data: None,
+            })
+            .collect()
+    }
+}
+
+fn collect_symbols(
+    source: &[u8],
+    namespace: Node,
+    path: &mut Vec<String>,
+    results: &mut Vec<IndexedSymbol>,
+) {
+    let mut cursor = namespace.walk();
+    for child in namespace.children(&mut cursor) {
+        let kind = match child.kind() {
+            "namespace_declaration" => SymbolKind::NAMESPACE,
+            "type_declaration" => symbol_kind_for_type(child.child_by_field_name("value")),
+            "event_declaration" => SymbolKind::EVENT,
+            "function_declaration" => SymbolKind::FUNCTION,
+            _ => continue,
+        };
+
+        let Some(name) = child.child_by_field_name("name") else {
+            continue;
+        };
+        let Ok(name_text) = name.utf8_text(source) else {
+            continue;
+        };
+
+        path.push(name_text.to_string());
+        results.push(IndexedSymbol {
+            name: path.join("."),
+            kind,
+            range: ts_range_to_lsp_range(name.range()),
+        });
+        if kind == SymbolKind::NAMESPACE {
+            collect_symbols(source, child, path, results);
+        }
+        path.pop();
+    }
+}
+
+/**
+    Scores the given candidate against a fuzzy query, where all characters
+    in the query must be present in the candidate, in order, ignoring casing.
+
+    Consecutive characters and characters that begin a new word,
+    or follow a namespace separator, give a higher score.
+
+    Returns `None` if the candidate does not match.
+*/
+fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let mut score = 0u32;
+    let mut query_chars = query.chars().filter(|c| !c.is_whitespace()).peekable();
+
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+    for c in candidate.chars() {
+        let Some(&q) = query_chars.peek() else {
+            break;
+        };
+
+        if c.eq_ignore_ascii_case(&q) {
+            query_chars.next();
+            score += 1;
+            if prev_matched {
+                score += 4;
+            }
+            let is_word_start = match prev {
+                None | Some('.' | '_') => true,
+                Some(p) => p.is_lowercase() && c.is_uppercase(),
+            };
+            if is_word_start {
+                score += 8;
+            }
+            prev_matched = true;
+        } else {
+            prev_matched = false;
+        }
+
+        prev = Some(c);
+    }
+
+    if query_chars.peek().is_some() {
+        None
+    } else {
+        Some(score)
+    }
+}
@@ -1,72 +1,155 @@
-use async_language_server::{tree_sitter::Node, tree_sitter_utils::find_child};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use async_language_server::{server::Document, tree_sitter::Node};
+use zap_analysis::Source;
+use zap_language::{
+    ast::{AstNode, OptionDecl},
+    tree_sitter_utils::AncestorIterator,
+};
+
+pub use zap_analysis::{
+    doc_comment, is_namespace, is_type, is_type_primitive, is_type_reference, namespace_path,
+    qualified_type_text, replace_bytes,
+};
 
 /**
-    Checks if the given node is a namespace declaration, or the root / source file.
+    Directories that are never searched when walking the workspace,
+    in addition to any hidden directories, such as `.git`.
 */
-pub fn is_namespace(node: Node) -> bool {
-    matches!(node.kind(), "source_file" | "namespace_declaration")
-}
+pub const IGNORED_DIRECTORIES: [&str; 3] = ["node_modules", "target", "out"];
 
 /**
-    Checks if the given node is a type, primitive or reference.
+    Creates an analysis source for the given document, borrowing its tree.
+
+    Returns `None` if the document has not been parsed.
 */
-pub fn is_type(node: Node) -> bool {
-    is_type_primitive(node) || is_type_reference(node)
+pub fn source(doc: &Document) -> Option<Source<'_>> {
+    Some(Source::from_root(doc.text_contents(), doc.node_at_root()?))
 }
 
 /**
-    Checks if the given node is a primitive type.
+    Finds the declaration of a top-level option in the given document.
 */
-pub fn is_type_primitive(node: Node) -> bool {
-    matches!(node.kind(), "primitive_type")
+pub fn find_option_declaration<'a>(doc: &'a Document, name: &str) -> Option<OptionDecl<'a>> {
+    let root = doc.node_at_root()?;
+    let mut cursor = root.walk();
+    root.children(&mut cursor)
+        .filter_map(OptionDecl::cast)
+        .find(|c| c.name().is_some_and(|n| doc.node_text(n) == name))
 }
 
 /**
-    Checks if the given identifier node is a type reference.
+    Finds the value of a top-level option in the given document,
+    with any surrounding quotes of string values removed.
+*/
+pub fn find_option_value(doc: &Document, name: &str) -> Option<String> {
+    find_option_declaration(doc, name)
+        .and_then(|c| c.value())
+        .map(|value: Node| {
+            doc.node_text(value)
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+}
 
-    Note that this does not check if the node is a **valid** type
-    reference, only that it is *trying* to reference some type.
+/**
+    Finds the value of a top-level path option in the given document, such as
+    `server_output`, resolved relative to the directory of the document.
 */
-pub fn is_type_reference(node: Node) -> bool {
-    // Type references are always classified as "identifier"
-    let is_ident_and_matches_this_node =
-        |child: Node| child == node && child.kind() == "identifier";
+pub fn find_option_path(doc: &Document, name: &str) -> Option<PathBuf> {
+    let value = find_option_value(doc, name)?;
+    let file = doc.url().to_file_path().ok()?;
+    Some(file.parent()?.join(value))
+}
 
-    // All type references have some kind of parent node
-    let Some(p) = node.parent() else {
-        return false;
-    };
+/**
+    Returns the path used to access an event or function declaration
+    through the generated API, such as `Inventory.ItemAdded`.
+*/
+pub fn api_path(doc: &Document, decl: Node) -> String {
+    let mut segments = AncestorIterator::new(decl)
+        .filter(|a| {
+            matches!(
+                a.kind(),
+                "namespace_declaration" | "event_declaration" | "function_declaration"
+            )
+        })
+        .filter_map(|a| a.child_by_field_name("name"))
+        .map(|name| doc.node_text(name))
+        .collect::<Vec<_>>();
+    segments.reverse();
+    segments.join(".")
+}
 
-    match p.kind() {
-        // These all have an inner "type" field
-        "namespaced_type" | "optional_type" | "property" | "set_type" => p
-            .child_by_field_name("type")
-            .is_some_and(is_ident_and_matches_this_node),
+/**
+    Applies the given `casing` option value to a name that is written in `PascalCase`.
 
-        // Maps have two inner fields "key_type" and "value_type"
-        "map_type" => {
-            p.child_by_field_name("key_type")
-                .is_some_and(is_ident_and_matches_this_node)
-                || p.child_by_field_name("value_type")
-                    .is_some_and(is_ident_and_matches_this_node)
+    Zap uses `PascalCase` for its generated API by default, so
+    unknown or missing casing values also return the name as-is.
+*/
+pub fn apply_casing(name: &str, casing: Option<&str>) -> String {
+    match casing {
+        Some("camelCase") => {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
         }
-
-        // Type declarations have an inner "value" field
-        "type_declaration" => p
-            .child_by_field_name("value")
-            .is_some_and(is_ident_and_matches_this_node),
-
-        // The data / args / rets fields have a single child that
-        // is not a keyword and is either a primitive or identifier
-        "event_data_field" | "function_args_field" | "function_rets_field" => {
-            find_child(p, is_ident_and_matches_this_node).is_some()
+        Some("snake_case") => {
+            let mut result = String::new();
+            for (index, c) in name.chars().enumerate() {
+                if c.is_uppercase() && index > 0 {
+                    result.push('_');
+                }
+                result.extend(c.to_lowercase());
+            }
+            result
         }
+        _ => name.to_string(),
+    }
+}
 
-        // The encompassing "type" node has at least a single inner
-        // child, unnamed, which is the actual type contents of it
-        "type" => p.child(0).is_some_and(is_ident_and_matches_this_node),
+/**
+    Recursively finds all files in the given root directories that pass the
+    given filter, skipping hidden and [`IGNORED_DIRECTORIES`] directories.
+
+    # Errors
 
-        // Nothing else can be a type reference according to grammar
-        _ => false,
+    - If any of the given root directories could not be read
+*/
+pub async fn find_workspace_files(
+    roots: Vec<PathBuf>,
+    filter: impl Fn(&Path) -> bool,
+) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = Vec::new();
+    for root in roots {
+        pending.push(tokio::fs::read_dir(&root).await?);
+    }
+    while let Some(mut reader) = pending.pop() {
+        while let Ok(Some(entry)) = reader.next_entry().await {
+            let path = entry.path();
+            let Ok(meta) = entry.metadata().await else {
+                continue;
+            };
+            if meta.is_dir() {
+                let ignored = entry.file_name().to_str().is_none_or(|name| {
+                    name.starts_with('.') || IGNORED_DIRECTORIES.contains(&name)
+                });
+                if ignored {
+                    continue;
+                }
+                if let Ok(reader) = tokio::fs::read_dir(&path).await {
+                    pending.push(reader);
+                }
+            } else if filter(&path) {
+                files.push(path);
+            }
+        }
     }
+    Ok(files)
 }
@@ -0,0 +1,144 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+use async_language_server::lsp_types::InitializeParams;
+use tokio::sync::Mutex;
+
+use crate::{references::FileScan, utils::find_workspace_files};
+
+/**
+    How long a single walk of the workspace files is reused for.
+
+    Created and deleted files are also picked up through watched file
+    notifications, but not all clients send those for non-Zap files.
+*/
+const FILES_CACHE_DURATION: Duration = Duration::from_secs(30);
+
+/**
+    The workspace folders opened by the client, along with a cached list of
+    all files found inside of them, and cached scans of individual files.
+
+    Cheap to clone, all clones share the same underlying state.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    roots: Arc<RwLock<Vec<PathBuf>>>,
+    files: Arc<Mutex<Option<(Instant, Arc<[PathBuf]>)>>>,
+    scans: Arc<RwLock<HashMap<PathBuf, (SystemTime, Arc<FileScan>)>>>,
+}
+
+impl Workspace {
+    /**
+        Sets the workspace root directories, using the workspace folders sent
+        by the client during initialization, or its root uri for older clients.
+    */
+    pub async fn initialize(&self, params: &InitializeParams) {
+        let mut roots = params
+            .workspace_folders
+            .iter()
+            .flatten()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect::<Vec<_>>();
+        if roots.is_empty() {
+            #[allow(deprecated)]
+            roots.extend(
+                params
+                    .root_uri
+                    .as_ref()
+                    .and_then(|uri| uri.to_file_path().ok()),
+            );
+        }
+
+        tracing::debug!("Using workspace roots {roots:?}");
+
+        if let Ok(mut current) = self.roots.write() {
+            *current = roots;
+        }
+        self.invalidate_files().await;
+    }
+
+    /**
+        Returns the workspace root directories.
+
+        Empty if the client has not opened any folder, such as when editing a single file.
+    */
+    pub fn roots(&self) -> Vec<PathBuf> {
+        self.roots
+            .read()
+            .map(|roots| roots.clone())
+            .unwrap_or_default()
+    }
+
+    /**
+        Returns all files in the workspace, walking the workspace roots
+        only if there is no recent walk that can be reused.
+
+        Concurrent callers wait for a single walk to finish, instead of walking again.
+
+        # Errors
+
+        - If any of the workspace roots could not be read
+    */
+    pub async fn files(&self) -> io::Result<Arc<[PathBuf]>> {
+        let mut cached = self.files.lock().await;
+        if let Some((walked_at, files)) = cached.as_ref() {
+            if walked_at.elapsed() < FILES_CACHE_DURATION {
+                return Ok(Arc::clone(files));
+            }
+        }
+
+        let files: Arc<[PathBuf]> = find_workspace_files(self.roots(), |_| true).await?.into();
+        *cached = Some((Instant::now(), Arc::clone(&files)));
+
+        // Scans of files that no longer exist will never be used again
+        if let Ok(mut scans) = self.scans.write() {
+            let existing = files.iter().collect::<HashSet<_>>();
+            scans.retain(|path, _| existing.contains(path));
+        }
+
+        tracing::debug!("Found {} workspace files", files.len());
+
+        Ok(files)
+    }
+
+    /**
+        Discards the cached list of workspace files, so
+        that the next call to [`Workspace::files`] walks again.
+    */
+    pub async fn invalidate_files(&self) {
+        self.files.lock().await.take();
+    }
+
+    /**
+        Returns the scan of the given file, scanning it only if it has
+        been modified since it was last scanned, or was never scanned.
+
+        Returns `None` if the file could not be read.
+    */
+    pub async fn scan(&self, path: &Path) -> Option<Arc<FileScan>> {
+        let modified = tokio::fs::metadata(path).await.ok()?.modified().ok()?;
+
+        let cached = self.scans.read().ok().and_then(|scans| {
+            scans
+                .get(path)
+                .filter(|(m, _)| *m == modified)
+                .map(|(_, scan)| Arc::clone(scan))
+        });
+        if cached.is_some() {
+            return cached;
+        }
+
+        let source = tokio::fs::read_to_string(path).await.ok()?;
+        let scan = Arc::new(FileScan::new(&source));
+        if let Ok(mut scans) = self.scans.write() {
+            scans.insert(path.to_path_buf(), (modified, Arc::clone(&scan)));
+        }
+
+        Some(scan)
+    }
+}
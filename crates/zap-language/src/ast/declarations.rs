@@ -0,0 +1,398 @@
+use tree_sitter::Node;
+
+use super::{AstNode, Type, ast_node, children};
+
+ast_node!(
+    /**
+        The root of a Zap file, containing all top-level declarations.
+    */
+    SourceFile,
+    "source_file"
+);
+
+impl<'a> SourceFile<'a> {
+    /**
+        Returns all top-level declarations, in order.
+    */
+    #[must_use]
+    pub fn declarations(&self) -> Vec<Declaration<'a>> {
+        children(self.0)
+    }
+}
+
+ast_node!(
+    /**
+        An option declaration, such as `opt casing = "PascalCase"`.
+    */
+    OptionDecl,
+    "option_declaration"
+);
+
+impl<'a> OptionDecl<'a> {
+    /**
+        Returns the `opt` keyword.
+    */
+    #[must_use]
+    pub fn keyword(&self) -> Option<Node<'a>> {
+        self.0.child(0)
+    }
+
+    /**
+        Returns the name of the option, such as `casing`.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child(1).filter(|n| n.kind() == "identifier")
+    }
+
+    /**
+        Returns the value of the option, such as `"PascalCase"`.
+    */
+    #[must_use]
+    pub fn value(&self) -> Option<Node<'a>> {
+        self.0.child(3)
+    }
+}
+
+ast_node!(
+    /**
+        A type declaration, such as `type Name = string`.
+    */
+    TypeDecl,
+    "type_declaration"
+);
+
+impl<'a> TypeDecl<'a> {
+    /**
+        Returns the `type` keyword.
+    */
+    #[must_use]
+    pub fn keyword(&self) -> Option<Node<'a>> {
+        self.0.child(0)
+    }
+
+    /**
+        Returns the name of the declared type.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("name")
+    }
+
+    /**
+        Returns the declared type itself.
+    */
+    #[must_use]
+    pub fn value(&self) -> Option<Type<'a>> {
+        self.0.child_by_field_name("value").and_then(Type::cast)
+    }
+}
+
+ast_node!(
+    /**
+        An event declaration, such as `event Name = { ... }`.
+    */
+    EventDecl,
+    "event_declaration"
+);
+
+impl<'a> EventDecl<'a> {
+    /**
+        Returns the `event` keyword.
+    */
+    #[must_use]
+    pub fn keyword(&self) -> Option<Node<'a>> {
+        self.0.child(0)
+    }
+
+    /**
+        Returns the name of the declared event.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("name")
+    }
+
+    /**
+        Returns all fields of the event, in order.
+    */
+    #[must_use]
+    pub fn fields(&self) -> Vec<FieldDecl<'a>> {
+        children(self.0)
+    }
+
+    /**
+        Returns the `from` field, if any.
+    */
+    #[must_use]
+    pub fn from_field(&self) -> Option<FieldDecl<'a>> {
+        self.field("event_from_field")
+    }
+
+    /**
+        Returns the `type` field, if any.
+    */
+    #[must_use]
+    pub fn type_field(&self) -> Option<FieldDecl<'a>> {
+        self.field("event_type_field")
+    }
+
+    /**
+        Returns the `call` field, if any.
+    */
+    #[must_use]
+    pub fn call_field(&self) -> Option<FieldDecl<'a>> {
+        self.field("event_call_field")
+    }
+
+    /**
+        Returns the `data` field, if any.
+    */
+    #[must_use]
+    pub fn data_field(&self) -> Option<FieldDecl<'a>> {
+        self.field("event_data_field")
+    }
+
+    fn field(&self, kind: &str) -> Option<FieldDecl<'a>> {
+        self.fields().into_iter().find(|f| f.0.kind() == kind)
+    }
+}
+
+ast_node!(
+    /**
+        A function declaration, such as `funct Name = { ... }`.
+    */
+    FunctDecl,
+    "function_declaration"
+);
+
+impl<'a> FunctDecl<'a> {
+    /**
+        Returns the `funct` keyword.
+    */
+    #[must_use]
+    pub fn keyword(&self) -> Option<Node<'a>> {
+        self.0.child(0)
+    }
+
+    /**
+        Returns the name of the declared function.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("name")
+    }
+
+    /**
+        Returns all fields of the function, in order.
+    */
+    #[must_use]
+    pub fn fields(&self) -> Vec<FieldDecl<'a>> {
+        children(self.0)
+    }
+
+    /**
+        Returns the `call` field, if any.
+    */
+    #[must_use]
+    pub fn call_field(&self) -> Option<FieldDecl<'a>> {
+        self.field("function_call_field")
+    }
+
+    /**
+        Returns the `args` field, if any.
+    */
+    #[must_use]
+    pub fn args_field(&self) -> Option<FieldDecl<'a>> {
+        self.field("function_args_field")
+    }
+
+    /**
+        Returns the `rets` field, if any.
+    */
+    #[must_use]
+    pub fn rets_field(&self) -> Option<FieldDecl<'a>> {
+        self.field("function_rets_field")
+    }
+
+    fn field(&self, kind: &str) -> Option<FieldDecl<'a>> {
+        self.fields().into_iter().find(|f| f.0.kind() == kind)
+    }
+}
+
+ast_node!(
+    /**
+        A single field of an event or function declaration, such as `from: Server`.
+    */
+    FieldDecl,
+    "event_from_field"
+        | "event_type_field"
+        | "event_call_field"
+        | "event_data_field"
+        | "function_call_field"
+        | "function_args_field"
+        | "function_rets_field"
+);
+
+impl<'a> FieldDecl<'a> {
+    /**
+        Returns the name of the field, such as `from`, without any event or function prefix.
+    */
+    #[must_use]
+    pub fn key(&self) -> &'static str {
+        self.0
+            .kind()
+            .trim_start_matches("event_")
+            .trim_start_matches("function_")
+            .trim_end_matches("_field")
+    }
+
+    /**
+        Returns the node for the name of the field, such as `from`.
+    */
+    #[must_use]
+    pub fn key_node(&self) -> Option<Node<'a>> {
+        self.0.child(0)
+    }
+
+    /**
+        Returns the value of the field, such as `Server`, or a type for payload fields.
+    */
+    #[must_use]
+    pub fn value(&self) -> Option<Node<'a>> {
+        self.0.child(2)
+    }
+
+    /**
+        Returns the value of the field as a type, if it is a payload field with a type value.
+    */
+    #[must_use]
+    pub fn value_type(&self) -> Option<Type<'a>> {
+        self.value().and_then(Type::cast)
+    }
+}
+
+ast_node!(
+    /**
+        A namespace declaration, such as `namespace Name = { ... }`.
+    */
+    NamespaceDecl,
+    "namespace_declaration"
+);
+
+impl<'a> NamespaceDecl<'a> {
+    /**
+        Returns the `namespace` keyword.
+    */
+    #[must_use]
+    pub fn keyword(&self) -> Option<Node<'a>> {
+        self.0.child(0)
+    }
+
+    /**
+        Returns the name of the declared namespace.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("name")
+    }
+
+    /**
+        Returns the opening `{` of the namespace body.
+    */
+    #[must_use]
+    pub fn open_brace(&self) -> Option<Node<'a>> {
+        let mut cursor = self.0.walk();
+        self.0.children(&mut cursor).find(|c| c.kind() == "{")
+    }
+
+    /**
+        Returns the closing `}` of the namespace body.
+    */
+    #[must_use]
+    pub fn close_brace(&self) -> Option<Node<'a>> {
+        let mut cursor = self.0.walk();
+        self.0.children(&mut cursor).find(|c| c.kind() == "}")
+    }
+
+    /**
+        Returns all declarations directly inside of the namespace, in order.
+    */
+    #[must_use]
+    pub fn declarations(&self) -> Vec<Declaration<'a>> {
+        children(self.0)
+    }
+}
+
+/**
+    Any kind of declaration, top-level or inside of a namespace.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Declaration<'a> {
+    Option(OptionDecl<'a>),
+    Type(TypeDecl<'a>),
+    Event(EventDecl<'a>),
+    Funct(FunctDecl<'a>),
+    Namespace(NamespaceDecl<'a>),
+}
+
+impl<'a> Declaration<'a> {
+    /**
+        Returns the keyword of the declaration, such as `type` or `event`.
+    */
+    #[must_use]
+    pub fn keyword(&self) -> Option<Node<'a>> {
+        match self {
+            Self::Option(decl) => decl.keyword(),
+            Self::Type(decl) => decl.keyword(),
+            Self::Event(decl) => decl.keyword(),
+            Self::Funct(decl) => decl.keyword(),
+            Self::Namespace(decl) => decl.keyword(),
+        }
+    }
+
+    /**
+        Returns the name of the declaration.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        match self {
+            Self::Option(decl) => decl.name(),
+            Self::Type(decl) => decl.name(),
+            Self::Event(decl) => decl.name(),
+            Self::Funct(decl) => decl.name(),
+            Self::Namespace(decl) => decl.name(),
+        }
+    }
+}
+
+impl<'a> AstNode<'a> for Declaration<'a> {
+    fn can_cast(kind: &str) -> bool {
+        OptionDecl::can_cast(kind)
+            || TypeDecl::can_cast(kind)
+            || EventDecl::can_cast(kind)
+            || FunctDecl::can_cast(kind)
+            || NamespaceDecl::can_cast(kind)
+    }
+
+    fn cast(node: Node<'a>) -> Option<Self> {
+        match node.kind() {
+            "option_declaration" => Some(Self::Option(OptionDecl(node))),
+            "type_declaration" => Some(Self::Type(TypeDecl(node))),
+            "event_declaration" => Some(Self::Event(EventDecl(node))),
+            "function_declaration" => Some(Self::Funct(FunctDecl(node))),
+            "namespace_declaration" => Some(Self::Namespace(NamespaceDecl(node))),
+            _ => None,
+        }
+    }
+
+    fn syntax(&self) -> Node<'a> {
+        match self {
+            Self::Option(decl) => decl.syntax(),
+            Self::Type(decl) => decl.syntax(),
+            Self::Event(decl) => decl.syntax(),
+            Self::Funct(decl) => decl.syntax(),
+            Self::Namespace(decl) => decl.syntax(),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use tree_sitter::Node;
+
+mod declarations;
+mod types;
+mod visitor;
+
+pub use self::declarations::{
+    Declaration, EventDecl, FieldDecl, FunctDecl, NamespaceDecl, OptionDecl, SourceFile, TypeDecl,
+};
+pub use self::types::{
+    EnumType, EnumVariant, MapType, NamespacedType, OptionalType, PrimitiveType, Property, SetType,
+    StructType, TupleType, TupleValue, Type, WrappedType,
+};
+pub use self::visitor::{
+    Visitor, visit, walk_declaration, walk_event_decl, walk_funct_decl, walk_namespace_decl,
+    walk_source_file, walk_type, walk_type_decl,
+};
+
+/**
+    A typed wrapper over a single tree-sitter node.
+
+    Wrappers are cheap to copy, can only be constructed from nodes of the
+    matching kind, and expose the children of those nodes through fallible,
+    named accessors, instead of positional `child(n)` lookups and kind strings.
+*/
+pub trait AstNode<'a>: Copy {
+    /**
+        Checks if a node of the given kind can be wrapped by this type.
+    */
+    fn can_cast(kind: &str) -> bool;
+
+    /**
+        Wraps the given node, if it is of the correct kind.
+    */
+    fn cast(node: Node<'a>) -> Option<Self>;
+
+    /**
+        Returns the underlying tree-sitter node.
+    */
+    fn syntax(&self) -> Node<'a>;
+}
+
+/**
+    Declares a newtype wrapper over nodes of the given kinds, and implements [`AstNode`] for it.
+*/
+macro_rules! ast_node {
+    ($(#[$meta:meta])* $name:ident, $($kind:literal)|+) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $name<'a>(tree_sitter::Node<'a>);
+
+        impl<'a> $crate::ast::AstNode<'a> for $name<'a> {
+            fn can_cast(kind: &str) -> bool {
+                matches!(kind, $($kind)|+)
+            }
+
+            fn cast(node: tree_sitter::Node<'a>) -> Option<Self> {
+                Self::can_cast(node.kind()).then_some(Self(node))
+            }
+
+            fn syntax(&self) -> tree_sitter::Node<'a> {
+                self.0
+            }
+        }
+
+        impl<'a> AsRef<tree_sitter::Node<'a>> for $name<'a> {
+            fn as_ref(&self) -> &tree_sitter::Node<'a> {
+                &self.0
+            }
+        }
+    };
+}
+
+pub(crate) use ast_node;
+
+/**
+    Returns all children of the given node that can be wrapped by `T`, in order.
+*/
+pub(crate) fn children<'a, T: AstNode<'a>>(node: Node<'a>) -> Vec<T> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor).filter_map(T::cast).collect()
+}
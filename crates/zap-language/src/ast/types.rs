@@ -0,0 +1,363 @@
+use tree_sitter::Node;
+
+use super::{AstNode, ast_node, children};
+
+ast_node!(
+    /**
+        A primitive type, such as `u8`, `string.utf8` or `Instance(Part)`.
+    */
+    PrimitiveType,
+    "primitive_type"
+);
+
+impl<'a> PrimitiveType<'a> {
+    /**
+        Returns the name of the primitive, such as `u8` or `string`.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child(0)
+    }
+
+    /**
+        Returns the separator between the name and the specifier, either `.` or `(`, if any.
+    */
+    #[must_use]
+    pub fn separator(&self) -> Option<Node<'a>> {
+        self.0.child(1)
+    }
+
+    /**
+        Returns the specifier of the primitive, such as `utf8` or `Part`, if any.
+    */
+    #[must_use]
+    pub fn specifier(&self) -> Option<Node<'a>> {
+        self.0.child(2)
+    }
+}
+
+ast_node!(
+    /**
+        A type reference through one or more namespaces, such as `A.B.C`.
+    */
+    NamespacedType,
+    "namespaced_type"
+);
+
+impl<'a> NamespacedType<'a> {
+    /**
+        Returns the namespaces walked by the reference, such as `A` and `B`, outermost first.
+    */
+    #[must_use]
+    pub fn namespaces(&self) -> Vec<Node<'a>> {
+        let mut cursor = self.0.walk();
+        self.0
+            .children_by_field_name("namespace", &mut cursor)
+            .collect()
+    }
+
+    /**
+        Returns the name of the referenced type, such as `C`.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("type")
+    }
+}
+
+ast_node!(
+    /**
+        An optional type, such as `u8?`.
+    */
+    OptionalType,
+    "optional_type"
+);
+
+impl<'a> OptionalType<'a> {
+    /**
+        Returns the inner type, such as `u8`.
+    */
+    #[must_use]
+    pub fn inner(&self) -> Option<Type<'a>> {
+        self.0.child(0).and_then(Type::cast)
+    }
+}
+
+ast_node!(
+    /**
+        A struct type, such as `struct { name: string }`.
+    */
+    StructType,
+    "struct_type"
+);
+
+impl<'a> StructType<'a> {
+    /**
+        Returns all properties of the struct, in order.
+    */
+    #[must_use]
+    pub fn properties(&self) -> Vec<Property<'a>> {
+        children(self.0)
+    }
+}
+
+ast_node!(
+    /**
+        A single property of a struct or tagged enum variant, such as `name: string`.
+    */
+    Property,
+    "property"
+);
+
+impl<'a> Property<'a> {
+    /**
+        Returns the name of the property.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("name")
+    }
+
+    /**
+        Returns the type of the property.
+    */
+    #[must_use]
+    pub fn ty(&self) -> Option<Type<'a>> {
+        self.0.child_by_field_name("type").and_then(Type::cast)
+    }
+}
+
+ast_node!(
+    /**
+        An enum type, either untagged such as `enum { A, B }`,
+        or tagged such as `enum "Kind" { A { ... }, B { ... } }`.
+    */
+    EnumType,
+    "enum_type"
+);
+
+impl<'a> EnumType<'a> {
+    /**
+        Returns the tag of the enum, such as `"Kind"`, if it is a tagged enum.
+    */
+    #[must_use]
+    pub fn tag(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("tag")
+    }
+
+    /**
+        Returns all variants of the enum, in order.
+    */
+    #[must_use]
+    pub fn variants(&self) -> Vec<EnumVariant<'a>> {
+        children(self.0)
+    }
+}
+
+ast_node!(
+    /**
+        A single variant of an enum, such as `A`, or `A { ... }` for tagged enums.
+    */
+    EnumVariant,
+    "enum_variant"
+);
+
+impl<'a> EnumVariant<'a> {
+    /**
+        Returns the name of the variant.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child(0).filter(|n| n.kind() == "identifier")
+    }
+
+    /**
+        Returns all properties of the variant, in order, if it is a tagged enum variant.
+    */
+    #[must_use]
+    pub fn properties(&self) -> Vec<Property<'a>> {
+        children(self.0)
+    }
+}
+
+ast_node!(
+    /**
+        A set type, such as `set { string }`.
+    */
+    SetType,
+    "set_type"
+);
+
+impl<'a> SetType<'a> {
+    /**
+        Returns the type of the set elements.
+    */
+    #[must_use]
+    pub fn ty(&self) -> Option<Type<'a>> {
+        self.0.child_by_field_name("type").and_then(Type::cast)
+    }
+}
+
+ast_node!(
+    /**
+        A map type, such as `map { [string]: u8 }`.
+    */
+    MapType,
+    "map_type"
+);
+
+impl<'a> MapType<'a> {
+    /**
+        Returns the type of the map keys.
+    */
+    #[must_use]
+    pub fn key(&self) -> Option<Type<'a>> {
+        self.0.child_by_field_name("key_type").and_then(Type::cast)
+    }
+
+    /**
+        Returns the type of the map values.
+    */
+    #[must_use]
+    pub fn value(&self) -> Option<Type<'a>> {
+        self.0
+            .child_by_field_name("value_type")
+            .and_then(Type::cast)
+    }
+}
+
+ast_node!(
+    /**
+        A tuple of values, such as function arguments `(name: string, u8)`.
+    */
+    TupleType,
+    "tuple"
+);
+
+impl<'a> TupleType<'a> {
+    /**
+        Returns all values of the tuple, in order.
+    */
+    #[must_use]
+    pub fn values(&self) -> Vec<TupleValue<'a>> {
+        children(self.0)
+    }
+}
+
+ast_node!(
+    /**
+        A single value of a tuple, either named such as `name: string`, or unnamed.
+    */
+    TupleValue,
+    "tuple_value"
+);
+
+impl<'a> TupleValue<'a> {
+    /**
+        Returns the name of the value, if it is named.
+    */
+    #[must_use]
+    pub fn name(&self) -> Option<Node<'a>> {
+        self.0.child_by_field_name("name")
+    }
+
+    /**
+        Returns the type of the value.
+    */
+    #[must_use]
+    pub fn ty(&self) -> Option<Type<'a>> {
+        self.0.child_by_field_name("type").and_then(Type::cast)
+    }
+}
+
+ast_node!(
+    /**
+        The encompassing `type` node, wrapping an inner type such as a
+        primitive or reference, along with any range or array constraints.
+    */
+    WrappedType,
+    "type"
+);
+
+impl<'a> WrappedType<'a> {
+    /**
+        Returns the inner type.
+    */
+    #[must_use]
+    pub fn inner(&self) -> Option<Type<'a>> {
+        self.0.child(0).and_then(Type::cast)
+    }
+}
+
+/**
+    Any kind of type, in any position that a type may be written in.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type<'a> {
+    /// `Name`
+    Reference(Node<'a>),
+    /// `u8`, `string.utf8`, ...
+    Primitive(PrimitiveType<'a>),
+    /// `A.B.C`
+    Namespaced(NamespacedType<'a>),
+    /// `u8?`
+    Optional(OptionalType<'a>),
+    /// `struct { ... }`
+    Struct(StructType<'a>),
+    /// `enum { ... }`
+    Enum(EnumType<'a>),
+    /// `set { ... }`
+    Set(SetType<'a>),
+    /// `map { ... }`
+    Map(MapType<'a>),
+    /// `( ... )`
+    Tuple(TupleType<'a>),
+    /// `type`
+    Wrapped(WrappedType<'a>),
+}
+
+impl<'a> AstNode<'a> for Type<'a> {
+    fn can_cast(kind: &str) -> bool {
+        kind == "identifier"
+            || PrimitiveType::can_cast(kind)
+            || NamespacedType::can_cast(kind)
+            || OptionalType::can_cast(kind)
+            || StructType::can_cast(kind)
+            || EnumType::can_cast(kind)
+            || SetType::can_cast(kind)
+            || MapType::can_cast(kind)
+            || TupleType::can_cast(kind)
+            || WrappedType::can_cast(kind)
+    }
+
+    fn cast(node: Node<'a>) -> Option<Self> {
+        match node.kind() {
+            "identifier" => Some(Self::Reference(node)),
+            "primitive_type" => Some(Self::Primitive(PrimitiveType(node))),
+            "namespaced_type" => Some(Self::Namespaced(NamespacedType(node))),
+            "optional_type" => Some(Self::Optional(OptionalType(node))),
+            "struct_type" => Some(Self::Struct(StructType(node))),
+            "enum_type" => Some(Self::Enum(EnumType(node))),
+            "set_type" => Some(Self::Set(SetType(node))),
+            "map_type" => Some(Self::Map(MapType(node))),
+            "tuple" => Some(Self::Tuple(TupleType(node))),
+            "type" => Some(Self::Wrapped(WrappedType(node))),
+            _ => None,
+        }
+    }
+
+    fn syntax(&self) -> Node<'a> {
+        match self {
+            Self::Reference(node) => *node,
+            Self::Primitive(typ) => typ.syntax(),
+            Self::Namespaced(typ) => typ.syntax(),
+            Self::Optional(typ) => typ.syntax(),
+            Self::Struct(typ) => typ.syntax(),
+            Self::Enum(typ) => typ.syntax(),
+            Self::Set(typ) => typ.syntax(),
+            Self::Map(typ) => typ.syntax(),
+            Self::Tuple(typ) => typ.syntax(),
+            Self::Wrapped(typ) => typ.syntax(),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use tree_sitter::Node;
+
+use super::{
+    AstNode, Declaration, EnumVariant, EventDecl, FunctDecl, NamespaceDecl, OptionDecl, Property,
+    SourceFile, TupleValue, Type, TypeDecl,
+};
+
+/**
+    A visitor over typed nodes, walking declarations and types in positional order.
+
+    All methods have default implementations that walk into the children of the
+    visited node, so implementors only need to override the methods they care
+    about, and call the matching `walk_*` function to keep walking deeper.
+
+    ### Example
+
+    ```rust ignore
+    struct References<'a>(Vec<Node<'a>>);
+
+    impl<'a> Visitor<'a> for References<'a> {
+        fn visit_type(&mut self, typ: Type<'a>) {
+            if let Type::Reference(node) = typ {
+                self.0.push(node);
+            }
+            walk_type(self, typ);
+        }
+    }
+    ```
+*/
+pub trait Visitor<'a> {
+    fn visit_source_file(&mut self, file: SourceFile<'a>) {
+        walk_source_file(self, file);
+    }
+
+    fn visit_declaration(&mut self, decl: Declaration<'a>) {
+        walk_declaration(self, decl);
+    }
+
+    fn visit_option_decl(&mut self, _decl: OptionDecl<'a>) {}
+
+    fn visit_type_decl(&mut self, decl: TypeDecl<'a>) {
+        walk_type_decl(self, decl);
+    }
+
+    fn visit_event_decl(&mut self, decl: EventDecl<'a>) {
+        walk_event_decl(self, decl);
+    }
+
+    fn visit_funct_decl(&mut self, decl: FunctDecl<'a>) {
+        walk_funct_decl(self, decl);
+    }
+
+    fn visit_namespace_decl(&mut self, decl: NamespaceDecl<'a>) {
+        walk_namespace_decl(self, decl);
+    }
+
+    fn visit_type(&mut self, typ: Type<'a>) {
+        walk_type(self, typ);
+    }
+}
+
+/**
+    Visits the given node with the given visitor, dispatching to the most
+    specific `visit_*` method for it - any source file, declaration, or type.
+
+    Other nodes, such as struct properties, are walked through until a node
+    that can be visited is found, meaning this can be called on any subtree.
+*/
+pub fn visit<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, node: Node<'a>) {
+    if let Some(file) = SourceFile::cast(node) {
+        visitor.visit_source_file(file);
+    } else if let Some(decl) = Declaration::cast(node) {
+        visitor.visit_declaration(decl);
+    } else if let Some(typ) = Type::cast(node) {
+        visitor.visit_type(typ);
+    } else {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            visit(visitor, child);
+        }
+    }
+}
+
+pub fn walk_source_file<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, file: SourceFile<'a>) {
+    for decl in file.declarations() {
+        visitor.visit_declaration(decl);
+    }
+}
+
+pub fn walk_declaration<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, decl: Declaration<'a>) {
+    match decl {
+        Declaration::Option(decl) => visitor.visit_option_decl(decl),
+        Declaration::Type(decl) => visitor.visit_type_decl(decl),
+        Declaration::Event(decl) => visitor.visit_event_decl(decl),
+        Declaration::Funct(decl) => visitor.visit_funct_decl(decl),
+        Declaration::Namespace(decl) => visitor.visit_namespace_decl(decl),
+    }
+}
+
+pub fn walk_type_decl<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, decl: TypeDecl<'a>) {
+    if let Some(value) = decl.value() {
+        visitor.visit_type(value);
+    }
+}
+
+pub fn walk_event_decl<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, decl: EventDecl<'a>) {
+    for field in decl.fields() {
+        if let Some(value) = field.value_type() {
+            visitor.visit_type(value);
+        }
+    }
+}
+
+pub fn walk_funct_decl<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, decl: FunctDecl<'a>) {
+    for field in decl.fields() {
+        if let Some(value) = field.value_type() {
+            visitor.visit_type(value);
+        }
+    }
+}
+
+pub fn walk_namespace_decl<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, decl: NamespaceDecl<'a>) {
+    for inner in decl.declarations() {
+        visitor.visit_declaration(inner);
+    }
+}
+
+/**
+    Walks all types directly nested inside of the given type, such as struct
+    property types, enum variant property types, and tuple value types.
+
+    Type references, namespaced or not, have no nested types.
+*/
+pub fn walk_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, typ: Type<'a>) {
+    let nested = match typ {
+        Type::Reference(_) | Type::Primitive(_) | Type::Namespaced(_) => Vec::new(),
+        Type::Optional(typ) => typ.inner().into_iter().collect(),
+        Type::Struct(typ) => typ.properties().iter().filter_map(Property::ty).collect(),
+        Type::Enum(typ) => typ
+            .variants()
+            .iter()
+            .flat_map(EnumVariant::properties)
+            .filter_map(|p| p.ty())
+            .collect(),
+        Type::Set(typ) => typ.ty().into_iter().collect(),
+        Type::Map(typ) => typ.key().into_iter().chain(typ.value()).collect(),
+        Type::Tuple(typ) => typ.values().iter().filter_map(TupleValue::ty).collect(),
+        Type::Wrapped(typ) => typ.inner().into_iter().collect(),
+    };
+    for inner in nested {
+        visitor.visit_type(inner);
+    }
+}
@@ -0,0 +1,234 @@
+use std::path::PathBuf;
+
+use crate::{
+    diagnostics::{Diagnostic, internal_compiler_error},
+    guard::catch_panic,
+};
+
+/**
+    The kind of a single generated output file.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputKind {
+    Server,
+    Client,
+    Types,
+    Tooling,
+}
+
+impl OutputKind {
+    /**
+        Returns the name of this output kind, such as `server`.
+    */
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Server => "server",
+            Self::Client => "client",
+            Self::Types => "types",
+            Self::Tooling => "tooling",
+        }
+    }
+
+    /**
+        Returns the name of the option that configures the path for this output kind.
+    */
+    #[must_use]
+    pub const fn option_name(self) -> &'static str {
+        match self {
+            Self::Server => "server_output",
+            Self::Client => "client_output",
+            Self::Types => "types_output",
+            Self::Tooling => "tooling_output",
+        }
+    }
+}
+
+/**
+    A single file generated by the Zap code generator.
+*/
+#[derive(Debug, Clone)]
+pub struct OutputFile {
+    pub kind: OutputKind,
+    /// Path of the file, relative to the Zap file it was generated from
+    pub path: PathBuf,
+    pub code: String,
+    /// TypeScript definitions, if the `typescript` option is enabled
+    pub definitions: Option<String>,
+}
+
+/**
+    Runs the Zap code generator on the given Zap file contents.
+
+    Same as [`crate::diagnostics::parse`], any crash inside of the code generator
+    is reported as a diagnostic, and callers that need to stay responsive should
+    run this on a separate thread, with a time limit.
+
+    # Errors
+
+    - If the contents contain any errors, in which case all diagnostics are returned
+    - If the code generator crashed, in which case a single diagnostic is returned
+*/
+pub fn generate(contents: &str) -> Result<Vec<OutputFile>, Vec<Diagnostic>> {
+    catch_panic(|| generate_unguarded(contents)).unwrap_or_else(|message| {
+        Err(vec![internal_compiler_error(
+            contents,
+            format!("the code generator panicked: {message}"),
+        )])
+    })
+}
+
+fn generate_unguarded(contents: &str) -> Result<Vec<OutputFile>, Vec<Diagnostic>> {
+    let (code, diagnostics) = libzap::run(contents, true);
+    let Some(code) = code else {
+        return Err(diagnostics);
+    };
+
+    let file = |kind: OutputKind, output: libzap::Output| OutputFile {
+        kind,
+        path: output.path,
+        code: output.code,
+        definitions: output.defs,
+    };
+
+    let mut files = vec![
+        file(OutputKind::Server, code.server),
+        file(OutputKind::Client, code.client),
+    ];
+    files.extend(code.types.map(|output| file(OutputKind::Types, output)));
+    files.extend(code.tooling.map(|output| file(OutputKind::Tooling, output)));
+
+    Ok(files)
+}
+
+/**
+    A preview of the generated types for a single Zap type.
+*/
+#[derive(Debug, Clone)]
+pub struct TypePreview {
+    pub luau: String,
+    /// Only available if the `typescript` option is enabled
+    pub typescript: Option<String>,
+}
+
+const PREVIEW_TYPE_PREFIX: &str = "ZapLanguageServerPreview";
+
+/**
+    Previews the generated Luau and TypeScript types for the given Zap types,
+    by running the code generator on the given contents with the types appended
+    to it as new type declarations, and then extracting them from the output.
+
+    All type references in the given types must be qualified from the root.
+
+    Returns `None` if the contents with the appended types can not be generated,
+    or if any of the generated Luau types could not be found in the output.
+
+    This runs the code generator, so the same guidelines as for [`generate`] apply.
+*/
+#[must_use]
+pub fn preview_types(contents: &str, types: &[String]) -> Option<Vec<TypePreview>> {
+    let mut source = String::new();
+    let has_types_output = contents
+        .lines()
+        .any(|line| line.trim_start().starts_with("opt types_output"));
+    if !has_types_output {
+        source.push_str("opt types_output = \"types.luau\"\n");
+    }
+    source.push_str(contents);
+    for (index, typ) in types.iter().enumerate() {
+        source.push_str(&format!("\ntype {PREVIEW_TYPE_PREFIX}{index} = {typ}\n"));
+    }
+
+    let files = generate(&source).ok()?;
+    let types_file = files.iter().find(|file| file.kind == OutputKind::Types)?;
+
+    (0..types.len())
+        .map(|index| {
+            let name = format!("{PREVIEW_TYPE_PREFIX}{index}");
+            Some(TypePreview {
+                luau: extract_type(&types_file.code, &name)?,
+                typescript: files
+                    .iter()
+                    .filter_map(|file| file.definitions.as_deref())
+                    .find_map(|definitions| extract_type(definitions, &name)),
+            })
+        })
+        .collect()
+}
+
+/**
+    Extracts the value of a generated type declaration from the given code,
+    such as `{ a: number }` from `export type Name = { a: number }`.
+
+    Multi-line values are assumed to have all lines after the first one
+    indented, except for closing brackets, same as the code generator emits.
+*/
+fn extract_type(code: &str, name: &str) -> Option<String> {
+    let mut lines = code.lines().skip_while(|line| {
+        !line
+            .split_once('=')
+            .is_some_and(|(head, _)| head.split_whitespace().last() == Some(name))
+    });
+
+    let (_, first) = lines.next()?.split_once('=')?;
+    let mut value = first.trim().to_string();
+    for line in lines {
+        if line.starts_with(char::is_whitespace) {
+            value.push('\n');
+            value.push_str(line);
+        } else if line.starts_with(['}', ')', ']']) {
+            value.push('\n');
+            value.push_str(line);
+            break;
+        } else {
+            break;
+        }
+    }
+
+    Some(value.trim_end_matches(';').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_type;
+
+    #[test]
+    fn extract_single_line() {
+        let code = "export type Other = string\nexport type Name = { a: number }\n";
+        assert_eq!(extract_type(code, "Name").as_deref(), Some("{ a: number }"));
+    }
+
+    #[test]
+    fn extract_multi_line() {
+        let code =
+            "export type Name = {\n\ta: number,\n\tb: string,\n}\nexport type Other = string\n";
+        assert_eq!(
+            extract_type(code, "Name").as_deref(),
+            Some("{\n\ta: number,\n\tb: string,\n}")
+        );
+    }
+
+    #[test]
+    fn extract_stops_at_next_declaration() {
+        let code = "export type Name = number\nexport type Other = string\n";
+        assert_eq!(extract_type(code, "Name").as_deref(), Some("number"));
+    }
+
+    #[test]
+    fn extract_strips_trailing_semicolon() {
+        let code = "export type Name = { a: number };\n";
+        assert_eq!(extract_type(code, "Name").as_deref(), Some("{ a: number }"));
+    }
+
+    #[test]
+    fn extract_matches_full_name_only() {
+        let code = "export type Name10 = string\nexport type Name1 = number\n";
+        assert_eq!(extract_type(code, "Name1").as_deref(), Some("number"));
+    }
+
+    #[test]
+    fn extract_missing_returns_none() {
+        let code = "export type Other = string\n";
+        assert_eq!(extract_type(code, "Name"), None);
+    }
+}
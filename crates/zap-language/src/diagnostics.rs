@@ -1,12 +1,51 @@
+use codespan_reporting::diagnostic::Label;
+
+use crate::guard::catch_panic;
+
 pub type Diagnostic = codespan_reporting::diagnostic::Diagnostic<()>;
 pub type Severity = codespan_reporting::diagnostic::Severity;
 pub type LabelStyle = codespan_reporting::diagnostic::LabelStyle;
 
+/**
+    Runs the Zap compiler on the given contents, returning all of its diagnostics.
+
+    Any crash inside of the compiler is reported as a single diagnostic with
+    [`Severity::Bug`] instead of taking down the caller. The compiler may also
+    never finish for some contents, so callers that need to stay responsive
+    should run this on a separate thread, with a time limit.
+*/
 #[must_use]
 pub fn parse(contents: &str) -> Vec<Diagnostic> {
+    catch_panic(|| parse_unguarded(contents)).unwrap_or_else(|message| {
+        vec![internal_compiler_error(
+            contents,
+            format!("the compiler panicked: {message}"),
+        )]
+    })
+}
+
+fn parse_unguarded(contents: &str) -> Vec<Diagnostic> {
     libzap::parser::parse(contents)
         .1
         .into_iter()
         .map(|report| report.to_diagnostic(false))
         .collect()
 }
+
+/**
+    Creates a diagnostic with [`Severity::Bug`] for a failed compiler run
+    on the given contents, with the given reason for why it failed.
+*/
+#[must_use]
+pub fn internal_compiler_error(contents: &str, reason: impl Into<String>) -> Diagnostic {
+    // Diagnostics need a primary label to be shown, so we attach
+    // this one to the first line, rather than the whole document
+    let first_line_end = contents.find('\n').unwrap_or(contents.len());
+    Diagnostic::bug()
+        .with_message("internal compiler error")
+        .with_labels(vec![Label::primary((), 0..first_line_end)])
+        .with_notes(vec![
+            reason.into(),
+            String::from("this is a bug in the Zap compiler, other features will keep working"),
+        ])
+}
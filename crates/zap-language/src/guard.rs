@@ -0,0 +1,29 @@
+use std::{any::Any, panic};
+
+/**
+    Runs the given function, turning any panic inside of
+    it into its panic message instead of unwinding further.
+
+    # Errors
+
+    - If the function panicked, with the message that it panicked with
+*/
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(panic::AssertUnwindSafe(f))
+        .map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/**
+    Returns the message of a caught panic, or `unknown panic` if
+    the panic payload was something other than a string.
+*/
+#[must_use]
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
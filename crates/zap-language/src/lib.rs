@@ -1,6 +1,9 @@
+pub mod ast;
+pub mod codegen;
 pub mod constants;
 pub mod diagnostics;
 pub mod docs;
+pub mod guard;
 pub mod tree_sitter_utils;
 
 pub use tree_sitter;
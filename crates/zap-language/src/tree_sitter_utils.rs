@@ -201,3 +201,38 @@ impl<'a> Iterator for AtomIterator<'a> {
         self.inner.by_ref().find(|&node| is_atom(node))
     }
 }
+
+/**
+    An iterator over a node and **all** of its ancestors, from innermost to outermost.
+
+    The first item is always the node itself, and the last item is always the root node.
+*/
+#[derive(Debug, Clone)]
+pub struct AncestorIterator<'a> {
+    current: Option<Node<'a>>,
+}
+
+impl<'a> AncestorIterator<'a> {
+    #[must_use]
+    pub fn new(node: Node<'a>) -> Self {
+        Self::from(node)
+    }
+}
+
+impl<'a> From<Node<'a>> for AncestorIterator<'a> {
+    fn from(node: Node<'a>) -> Self {
+        Self {
+            current: Some(node),
+        }
+    }
+}
+
+impl<'a> Iterator for AncestorIterator<'a> {
+    type Item = Node<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current?;
+        self.current = node.parent();
+        Some(node)
+    }
+}
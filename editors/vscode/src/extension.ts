@@ -1,5 +1,7 @@
 import * as vscode from "vscode";
 
+import * as generate from "./generate";
+import * as output from "./output";
 import * as server from "./server";
 
 let context: vscode.ExtensionContext;
@@ -15,6 +17,28 @@ export function getExtensionContext(): vscode.ExtensionContext {
 export async function activate(ctx: vscode.ExtensionContext) {
 	context = ctx;
 
+	ctx.subscriptions.push(
+		vscode.commands.registerCommand("zap.showReferences", server.showReferences),
+		vscode.commands.registerCommand("zap.rename", server.renameAt),
+		vscode.commands.registerCommand(
+			"zap.showGeneratedOutput",
+			output.showGeneratedOutput,
+		),
+		vscode.commands.registerCommand(
+			"zap.generateActive",
+			generate.generateActive,
+		),
+	);
+
+	const provider = new output.GeneratedOutputProvider();
+	ctx.subscriptions.push(
+		provider,
+		vscode.workspace.registerTextDocumentContentProvider(
+			output.OUTPUT_SCHEME,
+			provider,
+		),
+	);
+
 	await server.start();
 }
 
@@ -0,0 +1,45 @@
+import * as vscode from "vscode";
+
+const GENERATE_COMMAND = "zap.generate";
+
+type GenerateResult = {
+	written: Array<string>;
+	diagnostics: Array<{ message: string }>;
+};
+
+/**
+	Generates and writes all output files for the given Zap document.
+
+	Errors are shown as diagnostics by the language server,
+	so here we only notify the user that generation failed.
+*/
+export const generate = async (doc: vscode.TextDocument) => {
+	const result = await vscode.commands.executeCommand<
+		GenerateResult | undefined
+	>(GENERATE_COMMAND, doc.uri.toString());
+	if (result === undefined || result === null) {
+		return;
+	}
+
+	if (result.diagnostics.length > 0) {
+		vscode.window.showErrorMessage(
+			`Failed to generate Zap output: ${result.diagnostics[0].message}`,
+		);
+	} else {
+		vscode.window.showInformationMessage(
+			`Generated ${result.written.length} Zap output file(s)`,
+		);
+	}
+};
+
+/**
+	Generates output files for the active Zap document.
+*/
+export const generateActive = async () => {
+	const editor = vscode.window.activeTextEditor;
+	if (editor === undefined || editor.document.languageId !== "zap") {
+		vscode.window.showWarningMessage("Open a Zap file to generate its output");
+		return;
+	}
+	await generate(editor.document);
+};
@@ -0,0 +1,165 @@
+import * as vscode from "vscode";
+
+/**
+	Scheme used for read-only virtual documents with generated output.
+
+	Virtual document uris look like `zap-output:/server.luau?<source uri>`,
+	where the path is the name of the generated output file, and the
+	query is the uri of the Zap document that it was generated from.
+*/
+export const OUTPUT_SCHEME = "zap-output";
+
+const OUTPUT_COMMAND = "zap.generatedOutput";
+const UPDATE_DELAY_MS = 250;
+
+type GeneratedOutput = {
+	outputs: Array<{
+		kind: string;
+		path: string;
+		code: string;
+		definitions: string | null;
+	}>;
+	diagnostics: Array<{
+		message: string;
+		range: { start: { line: number; character: number } };
+	}>;
+};
+
+/**
+	Provides generated output from the language server as virtual
+	documents, updating them whenever their source document changes.
+*/
+export class GeneratedOutputProvider
+	implements vscode.TextDocumentContentProvider, vscode.Disposable
+{
+	private readonly changed = new vscode.EventEmitter<vscode.Uri>();
+	private readonly pending = new Map<string, NodeJS.Timeout>();
+	private readonly listener: vscode.Disposable;
+
+	readonly onDidChange = this.changed.event;
+
+	constructor() {
+		this.listener = vscode.workspace.onDidChangeTextDocument((event) => {
+			if (event.document.languageId === "zap") {
+				this.scheduleUpdate(event.document.uri);
+			}
+		});
+	}
+
+	async provideTextDocumentContent(uri: vscode.Uri): Promise<string> {
+		const result = await vscode.commands.executeCommand<
+			GeneratedOutput | undefined
+		>(OUTPUT_COMMAND, uri.query);
+		if (result === undefined || result === null) {
+			return "-- The language server did not return any output";
+		}
+
+		if (result.diagnostics.length > 0) {
+			const lines = result.diagnostics.map(
+				(d) => `-- ${d.range.start.line + 1}:${d.range.start.character + 1} ${d.message}`,
+			);
+			return ["-- Failed to generate output", "--", ...lines].join("\n");
+		}
+
+		const name = uri.path.replace(/^\//, "");
+		for (const output of result.outputs) {
+			if (outputName(output.kind, output.path, false) === name) {
+				return output.code;
+			}
+			if (
+				output.definitions &&
+				outputName(output.kind, output.path, true) === name
+			) {
+				return output.definitions;
+			}
+		}
+
+		return `-- No generated output named '${name}'`;
+	}
+
+	private scheduleUpdate(source: vscode.Uri) {
+		const key = source.toString();
+		const existing = this.pending.get(key);
+		if (existing !== undefined) {
+			clearTimeout(existing);
+		}
+
+		this.pending.set(
+			key,
+			setTimeout(() => {
+				this.pending.delete(key);
+				for (const doc of vscode.workspace.textDocuments) {
+					if (doc.uri.scheme === OUTPUT_SCHEME && doc.uri.query === key) {
+						this.changed.fire(doc.uri);
+					}
+				}
+			}, UPDATE_DELAY_MS),
+		);
+	}
+
+	dispose() {
+		for (const timeout of this.pending.values()) {
+			clearTimeout(timeout);
+		}
+		this.listener.dispose();
+		this.changed.dispose();
+	}
+}
+
+/**
+	Lets the user pick one of the generated outputs for the active
+	Zap document, and opens it as a read-only virtual document.
+*/
+export const showGeneratedOutput = async () => {
+	const editor = vscode.window.activeTextEditor;
+	if (editor === undefined || editor.document.languageId !== "zap") {
+		vscode.window.showWarningMessage("Open a Zap file to show its generated output");
+		return;
+	}
+
+	const source = editor.document.uri.toString();
+	const result = await vscode.commands.executeCommand<
+		GeneratedOutput | undefined
+	>(OUTPUT_COMMAND, source);
+	if (result === undefined || result === null) {
+		return;
+	}
+	if (result.diagnostics.length > 0) {
+		vscode.window.showErrorMessage(
+			"Failed to generate output, fix all errors in the Zap file and try again",
+		);
+		return;
+	}
+
+	const names = result.outputs.flatMap((output) =>
+		output.definitions
+			? [
+					outputName(output.kind, output.path, false),
+					outputName(output.kind, output.path, true),
+				]
+			: [outputName(output.kind, output.path, false)],
+	);
+
+	const picked = await vscode.window.showQuickPick(names, {
+		placeHolder: "Select generated output to show",
+	});
+	if (picked === undefined) {
+		return;
+	}
+
+	const uri = vscode.Uri.from({
+		scheme: OUTPUT_SCHEME,
+		path: `/${picked}`,
+		query: source,
+	});
+	const doc = await vscode.workspace.openTextDocument(uri);
+	await vscode.window.showTextDocument(doc, {
+		viewColumn: vscode.ViewColumn.Beside,
+		preview: true,
+	});
+};
+
+const outputName = (kind: string, path: string, definitions: boolean) => {
+	const file = path.split(/[\\/]/).pop() || `${kind}.luau`;
+	return definitions ? file.replace(/\.luau?$/, ".d.ts") : file;
+};
@@ -76,6 +76,21 @@ export const start = async () => {
 	const clientOptions: LanguageClientOptions = {
 		stdioEncoding: "utf8",
 		documentSelector: [{ scheme: "file", language: "zap" }],
+		initializationOptions: {
+			generateOnSave: vscode.workspace
+				.getConfiguration("zap")
+				.get<boolean>("generateOnSave", false),
+		},
+		synchronize: {
+			// Sends settings such as `zap.generateOnSave` to the server whenever they change
+			configurationSection: "zap",
+			fileEvents: [
+				// Keeps the workspace symbol index up to date, even for unopened files
+				vscode.workspace.createFileSystemWatcher("**/*.zap"),
+				// Lets the server know when to look for new usages of the generated API
+				vscode.workspace.createFileSystemWatcher("**/*.{luau,lua,ts}"),
+			],
+		},
 		outputChannel,
 	};
 
@@ -93,6 +108,61 @@ export const start = async () => {
 	client.start();
 };
 
+/**
+	Starts renaming the symbol at the given position, using arguments sent by the language server.
+
+	Used after refactorings that create new symbols, such as extracting a
+	type, to let the user pick a name for it right away.
+*/
+export const renameAt = async (
+	uri: string,
+	position: { line: number; character: number },
+) => {
+	const c = client;
+	if (c === undefined) {
+		return;
+	}
+
+	const pos = c.protocol2CodeConverter.asPosition(position);
+	const editor = await vscode.window.showTextDocument(
+		c.protocol2CodeConverter.asUri(uri),
+	);
+
+	editor.selection = new vscode.Selection(pos, pos);
+
+	await vscode.commands.executeCommand("editor.action.rename");
+};
+
+/**
+	Opens the references view, using arguments sent by the language server.
+
+	Arguments are sent as plain LSP types, and must be converted
+	to their VSCode counterparts before showing any references.
+*/
+export const showReferences = async (
+	uri: string,
+	position: { line: number; character: number },
+	locations: Array<{
+		uri: string;
+		range: {
+			start: { line: number; character: number };
+			end: { line: number; character: number };
+		};
+	}>,
+) => {
+	const c = client;
+	if (c === undefined) {
+		return;
+	}
+
+	await vscode.commands.executeCommand(
+		"editor.action.showReferences",
+		c.protocol2CodeConverter.asUri(uri),
+		c.protocol2CodeConverter.asPosition(position),
+		locations.map((location) => c.protocol2CodeConverter.asLocation(location)),
+	);
+};
+
 /**
 	Stops the language server.
 